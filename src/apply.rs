@@ -0,0 +1,186 @@
+use std::io;
+use std::time::{Duration, Instant};
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Clear,Paragraph,Widget},
+};
+use crate::error::{Error, ErrorPopup};
+use crate::monitor::Monitor;
+use crate::utils::TUIMode;
+use crate::App;
+
+pub const REVERT_DELAY: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone)]
+pub struct PendingApply {
+    // the compositor layout read before the apply
+    pub previous: Vec<Monitor>,
+    // the edited monitors, restored on revert
+    pub monitors: Vec<Monitor>,
+    pub deadline: Instant,
+}
+
+#[derive(Debug)]
+pub struct ApplyConfirm {
+    pub remaining: u64,
+}
+
+impl ApplyConfirm {
+    pub fn new(pending: &PendingApply) -> Self {
+        ApplyConfirm {
+            remaining: pending.deadline
+                .saturating_duration_since(Instant::now())
+                .as_secs_f64()
+                .ceil() as u64,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => ApplyConfirm::confirm(app),
+            KeyCode::Char('n') | KeyCode::Esc => ApplyConfirm::revert(app),
+            _ => {}
+        }
+    }
+
+    pub fn start(app:&mut App) {
+        ApplyConfirm::start_with(app, |app| app.backend.apply(&Monitor::connected(&app.monitors)));
+    }
+
+    // the previous layout is always read and restored through the backend
+    pub fn start_with(app:&mut App, apply: impl FnOnce(&mut App) -> io::Result<()>) {
+        let previous = app.backend.get_monitors().map_err(Error::Backend);
+        let Some(previous) = ErrorPopup::report(app, previous) else {
+            return;
+        };
+        let applied = apply(app).map_err(Error::Backend);
+        if ErrorPopup::report(app, applied).is_none() {
+            return;
+        }
+        app.pending_apply = Some(PendingApply {
+            previous,
+            monitors: app.monitors.clone(),
+            deadline: Instant::now() + REVERT_DELAY,
+        });
+        app.mode = TUIMode::Apply;
+    }
+
+    pub fn tick(app:&mut App) {
+        if let Some(pending) = &app.pending_apply
+            && Instant::now() >= pending.deadline {
+            ApplyConfirm::revert(app);
+        }
+    }
+
+    fn confirm(app:&mut App) {
+        app.pending_apply = None;
+        app.status.info("Kept the applied layout");
+        app.mode = TUIMode::View;
+    }
+
+    fn revert(app:&mut App) {
+        if let Some(pending) = app.pending_apply.take() {
+            let reverted = app.backend.apply(&pending.previous).map_err(Error::Backend);
+            if ErrorPopup::report(app, reverted).is_some() {
+                app.status.warning("Reverted to the previous layout");
+                // the edits are kept, only the compositor goes back
+                if app.monitors != pending.monitors {
+                    let before = std::mem::replace(&mut app.monitors, pending.monitors);
+                    app.undo.record(before, false);
+                }
+                if app.selected_monitor >= app.monitors.len() {
+                    app.selected_monitor = app.monitors.len().saturating_sub(1);
+                }
+            }
+        }
+        app.mode = TUIMode::View;
+    }
+
+    pub fn popup_area(area: Rect) -> Rect {
+        let width = area.width.min(44);
+        let height = area.height.min(6);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Apply ".white().bold());
+        let instructions = Line::from(vec![
+            " Keep ".white(),
+            "<y> ".blue().bold(),
+            " Revert ".white(),
+            "<n> ".blue().bold(),
+        ]);
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let text = vec![
+            Line::from(""),
+            Line::from("Keep this display configuration ?").centered(),
+            Line::from(format!("Reverting in {} seconds", self.remaining)).centered(),
+        ];
+
+        Clear.render(area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn render_apply_confirm() {
+        let popup = ApplyConfirm { remaining: 12 };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 44, 6));
+
+        popup.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━━━━━━━━ Apply ━━━━━━━━━━━━━━━━━━┓",
+            "┃                                          ┃",
+            "┃     Keep this display configuration ?    ┃",
+            "┃          Reverting in 12 seconds         ┃",
+            "┃                                          ┃",
+            "┗━━━━━━━━━━ Keep <y>  Revert <n> ━━━━━━━━━━┛",
+        ]);
+
+        let border_style = Style::new().fg(Color::Yellow);
+        let title_style = Style::new().bold().fg(Color::White);
+        let instructions_label_style = Style::new().fg(Color::White);
+        let instructions_key_style = Style::new().blue().bold();
+
+        expected.set_style(Rect::new(0, 0, 18, 1), border_style);
+        expected.set_style(Rect::new(18, 0, 7, 1), title_style);
+        expected.set_style(Rect::new(25, 0, 19, 1), border_style);
+
+        for y in 1..5 {
+            expected.set_style(Rect::new(0, y, 1, 1), border_style);
+            expected.set_style(Rect::new(43, y, 1, 1), border_style);
+        }
+
+        expected.set_style(Rect::new(0, 5, 11, 1), border_style);
+        expected.set_style(Rect::new(11, 5, 6, 1), instructions_label_style);
+        expected.set_style(Rect::new(17, 5, 4, 1), instructions_key_style);
+        expected.set_style(Rect::new(21, 5, 8, 1), instructions_label_style);
+        expected.set_style(Rect::new(29, 5, 4, 1), instructions_key_style);
+        expected.set_style(Rect::new(33, 5, 11, 1), border_style);
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,163 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::layout::{self, Arrangement};
+use crate::list::MonitorList;
+use crate::utils::TUIMode;
+use crate::App;
+
+#[derive(Debug)]
+pub struct Arrange{
+    pub by_list: bool,
+    pub state: TableState,
+}
+
+impl Arrange{
+    pub fn new(selected:usize, by_list: bool) -> Self {
+        Arrange {
+            by_list,
+            state: TableState::default()
+                .with_selected(selected),
+        }
+    }
+
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('k')=> Arrange::previous(app),
+            KeyCode::Char('j')=> Arrange::next(app),
+            KeyCode::Char('o')=> app.arrange_by_list = !app.arrange_by_list,
+            KeyCode::Char(' ')=> Arrange::select(app),
+            KeyCode::Esc => Arrange::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    fn next(app:&mut App) {
+        app.selected_arrangement = if app.selected_arrangement >= Arrangement::ALL.len() - 1 {
+            0
+        } else {
+            app.selected_arrangement + 1
+        }
+    }
+
+    fn previous(app:&mut App) {
+        app.selected_arrangement = if app.selected_arrangement == 0 {
+            Arrangement::ALL.len() - 1
+        } else {
+            app.selected_arrangement - 1
+        }
+    }
+
+    fn select(app:&mut App) {
+        let order = if app.arrange_by_list {
+            layout::list_order(&app.monitors, &MonitorList::order(&app.list_order, app.monitors.len()))
+        } else {
+            layout::position_order(&app.monitors)
+        };
+        layout::arrange(&mut app.monitors, Arrangement::ALL[app.selected_arrangement], &order);
+    }
+
+    fn arrangements_to_rows(&self) -> Vec<Row<'static>> {
+        Arrangement::ALL
+            .into_iter()
+            .map(|arrangement| {
+                Row::new(vec![
+                    Cell::default().content(
+                        Line::from(arrangement.name())
+                            .centered()
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Arrange{
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Arrange ".bold());
+        let order = Line::from(
+            if self.by_list {" Order: list "} else {" Order: position "}.white()
+        );
+        let block = Block::bordered()
+            .title(title.white().centered())
+            .title_bottom(order.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+
+        let widths = [
+            Constraint::Percentage(100),
+        ];   
+
+        let table = Table::new(self.arrangements_to_rows(),widths) 
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol(" ")            
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn render_arrange() {
+
+        let mut arrange = Arrange{
+            by_list: false,
+            state: TableState::default(),
+        }; 
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 5));
+        
+        arrange.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━ Arrange ━━━━━┓",
+            "┃       Row        ┃",
+            "┃      Column      ┃",
+            "┃       Grid       ┃",
+            "┗ Order: position ━┛",
+        ]);
+
+        let border_style = Style::new().fg(Color::Yellow);
+        let title_style = Style::new().bold().fg(Color::White);
+        let row_style = Style::new();
+
+        expected.set_style(Rect::new(0, 0, 5, 1), border_style);
+        expected.set_style(Rect::new(5, 0, 9, 1), title_style);
+        expected.set_style(Rect::new(14, 0, 6, 1), border_style);       
+
+        for i in 0..Arrangement::ALL.len() {
+            expected.set_style(Rect::new(0, (i + 1) as u16, 1, 1), border_style);
+            expected.set_style(Rect::new(1, (i + 1) as u16, 18, 1), row_style);
+            expected.set_style(Rect::new(19, (i + 1) as u16, 1, 1), border_style);
+        }
+
+        expected.set_style(Rect::new(0, 4, 20, 1), border_style);
+        expected.set_style(Rect::new(1, 4, 17, 1), Style::new().fg(Color::White));
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,75 @@
+use std::io;
+use serde::{Deserialize, Serialize};
+use crate::monitor::Monitor;
+
+mod hyprctl;
+mod sway;
+mod wlr_randr;
+#[cfg(test)]
+pub mod fake;
+
+pub use hyprctl::Hyprctl;
+pub use sway::Sway;
+pub use wlr_randr::WlrRandr;
+
+pub trait DisplayBackend: std::fmt::Debug {
+    fn get_monitors(&mut self) -> io::Result<Vec<Monitor>>;
+    fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendKind {
+    #[default]
+    WlrRandr,
+    Hyprctl,
+    Sway,
+}
+
+impl BackendKind {
+    pub fn create(self) -> Box<dyn DisplayBackend> {
+        match self {
+            BackendKind::WlrRandr => Box::new(WlrRandr::new(Box::new(SystemRunner))),
+            BackendKind::Hyprctl => Box::new(Hyprctl::new(Box::new(SystemRunner))),
+            BackendKind::Sway => Box::new(Sway::from_env()),
+        }
+    }
+}
+
+impl Default for Box<dyn DisplayBackend> {
+    fn default() -> Self {
+        BackendKind::default().create()
+    }
+}
+
+pub trait CommandRunner: std::fmt::Debug {
+    fn run(&mut self, program: &str, args: &[String]) -> io::Result<String>;
+}
+
+impl Default for Box<dyn CommandRunner> {
+    fn default() -> Self {
+        Box::new(SystemRunner)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&mut self, program: &str, args: &[String]) -> io::Result<String> {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| io::Error::new(e.kind(), format!("Failed to execute {}: {}", program, e)))?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "{} exited with {}: {}",
+                program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        String::from_utf8(output.stdout)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use crate::monitor::Monitor;
+use super::{CommandRunner, DisplayBackend};
+
+#[derive(Debug, Default, Clone)]
+pub struct FakeBackend {
+    pub monitors: Vec<Monitor>,
+    pub applied: Vec<Vec<Monitor>>,
+    // every call fails with this message when set
+    pub error: Option<String>,
+}
+
+impl FakeBackend {
+    pub fn new(monitors: Vec<Monitor>) -> Self {
+        FakeBackend {
+            monitors,
+            applied: vec![],
+            error: None,
+        }
+    }
+
+    fn check(&self) -> io::Result<()> {
+        match &self.error {
+            Some(message) => Err(io::Error::other(message.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl DisplayBackend for FakeBackend {
+    fn get_monitors(&mut self) -> io::Result<Vec<Monitor>> {
+        self.check()?;
+        Ok(self.monitors.clone())
+    }
+
+    fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()> {
+        self.check()?;
+        self.monitors = monitors.to_vec();
+        self.applied.push(monitors.to_vec());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FakeRunner {
+    pub responses: HashMap<String, String>,
+    pub calls: Rc<RefCell<Vec<Vec<String>>>>,
+}
+
+impl FakeRunner {
+    pub fn with_response(mut self, first_arg: &str, stdout: &str) -> Self {
+        self.responses.insert(first_arg.to_string(), stdout.to_string());
+        self
+    }
+}
+
+impl CommandRunner for FakeRunner {
+    fn run(&mut self, program: &str, args: &[String]) -> io::Result<String> {
+        let mut call = vec![program.to_string()];
+        call.extend_from_slice(args);
+        self.calls.borrow_mut().push(call);
+        Ok(args
+            .first()
+            .and_then(|arg| self.responses.get(arg))
+            .cloned()
+            .unwrap_or_default())
+    }
+}
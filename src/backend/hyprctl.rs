@@ -0,0 +1,190 @@
+use std::io;
+use serde::Deserialize;
+use crate::error;
+use crate::monitor::{deserialize_transform, Monitor, Position, Resolution, Workspace};
+use super::{CommandRunner, DisplayBackend};
+
+#[derive(Debug)]
+pub struct Hyprctl {
+    runner: Box<dyn CommandRunner>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HyprMonitor {
+    name: String,
+    description: Option<String>,
+    make: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+    width: i32,
+    height: i32,
+    refresh_rate: f32,
+    x: i32,
+    y: i32,
+    scale: f32,
+    // the rest of the model relies on transforms in 0..=7
+    #[serde(default, deserialize_with = "deserialize_transform")]
+    transform: u8,
+    #[serde(default)]
+    vrr: bool,
+    #[serde(default)]
+    mirror_of: Option<String>,
+    active_workspace: Option<Workspace>,
+    #[serde(default)]
+    focused: bool,
+    dpms_status: Option<bool>,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(default)]
+    available_modes: Vec<String>,
+}
+
+impl Hyprctl {
+    pub fn new(runner: Box<dyn CommandRunner>) -> Self {
+        Hyprctl { runner }
+    }
+
+    pub fn parse(json: &str) -> io::Result<Vec<Monitor>> {
+        let monitors: Vec<HyprMonitor> = serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Deserialization error: {}", e)))?;
+        Ok(monitors.into_iter().map(Monitor::from).collect())
+    }
+
+    pub fn parse_mode(mode: &str) -> Option<Resolution> {
+        let (size, refresh) = mode.trim_end_matches("Hz").split_once('@')?;
+        let (width, height) = size.split_once('x')?;
+        Some(Resolution {
+            width: width.parse().ok()?,
+            height: height.parse().ok()?,
+            refresh: refresh.parse().ok()?,
+            preferred: false,
+            current: false,
+        })
+    }
+
+    pub fn batch_command(monitors: &[Monitor]) -> error::Result<String> {
+        Ok(monitors
+            .iter()
+            .map(|monitor| Ok(format!("keyword monitor {}", monitor.to_hyprland_rule()?)))
+            .collect::<error::Result<Vec<String>>>()?
+            .join(" ; "))
+    }
+}
+
+impl From<HyprMonitor> for Monitor {
+    fn from(hypr: HyprMonitor) -> Self {
+        let mut modes: Vec<Resolution> = hypr.available_modes
+            .iter()
+            .filter_map(|mode| Hyprctl::parse_mode(mode))
+            .collect();
+        // hyprctl does not report the preferred mode, the first available one is the native mode
+        if let Some(first) = modes.first_mut() {
+            first.preferred = true;
+        }
+        if !hypr.disabled {
+            match modes.iter_mut().find(|m| {
+                m.width == hypr.width && m.height == hypr.height && (m.refresh - hypr.refresh_rate).abs() < 0.05
+            }) {
+                Some(mode) => mode.current = true,
+                None => modes.push(Resolution {
+                    width: hypr.width,
+                    height: hypr.height,
+                    refresh: hypr.refresh_rate,
+                    preferred: false,
+                    current: true,
+                }),
+            }
+        }
+        Monitor {
+            name: hypr.name,
+            description: hypr.description,
+            make: hypr.make,
+            model: hypr.model,
+            serial: hypr.serial,
+            enabled: !hypr.disabled,
+            modes,
+            position: Some(Position { x: hypr.x, y: hypr.y }),
+            scale: Some(hypr.scale),
+            transform: hypr.transform,
+            vrr: hypr.vrr,
+            mirror_of: hypr.mirror_of.filter(|name| name != "none"),
+            active_workspace: hypr.active_workspace.filter(|workspace| workspace.id >= 0),
+            focused: hypr.focused,
+            dpms_status: hypr.dpms_status,
+            extra_options: vec![],
+            disconnected: false,
+        }
+    }
+}
+
+impl DisplayBackend for Hyprctl {
+    fn get_monitors(&mut self) -> io::Result<Vec<Monitor>> {
+        let stdout = self.runner.run("hyprctl", &["monitors".to_string(), "all".to_string(), "-j".to_string()])?;
+        Hyprctl::parse(&stdout)
+    }
+
+    fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()> {
+        let stdout = self.runner.run("hyprctl", &["--batch".to_string(), Hyprctl::batch_command(monitors)?])?;
+        // hyprctl answers "ok" for every accepted keyword and still exits with 0 on errors
+        match stdout.lines().map(str::trim).find(|line| !line.is_empty() && *line != "ok") {
+            Some(error) => Err(io::Error::other(format!("hyprctl rejected the layout: {}", error))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hyprctl_docked_fixture() {
+        let monitors = Hyprctl::parse(include_str!("../../tests/fixtures/hyprctl_monitors_docked.json")).unwrap();
+        assert_eq!(monitors.len(), 2);
+
+        let laptop = &monitors[0];
+        assert_eq!(laptop.name, "eDP-1");
+        assert_eq!(laptop.description.as_deref(), Some("BOE 0x0BCA"));
+        assert!(laptop.enabled);
+        assert_eq!(laptop.modes.len(), 3);
+        assert!(laptop.modes[0].preferred);
+        assert!(laptop.modes[0].current);
+        assert_eq!(laptop.scale, Some(1.5));
+        assert_eq!(laptop.active_workspace.as_ref().unwrap().name, "1");
+        assert!(!laptop.focused);
+
+        let external = &monitors[1];
+        assert_eq!(external.position.clone().unwrap().x, 1504);
+        assert_eq!(external.get_current_resolution().unwrap().width, 2560);
+        assert_eq!(external.get_prefered_resolution().unwrap().width, 3840);
+        assert_eq!(external.transform, 1);
+        assert!(external.vrr);
+        assert!(external.focused);
+        assert_eq!(external.dpms_status, Some(true));
+        assert_eq!(external.mirror_of, None);
+    }
+
+    #[test]
+    fn reject_unknown_transform() {
+        let json = r#"[{"name":"DP-1","width":1920,"height":1080,"refreshRate":60.0,"x":0,"y":0,"scale":1.0,"transform":9}]"#;
+        let error = Hyprctl::parse(json).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("unknown transform 9"));
+        assert_eq!(Hyprctl::parse(&json.replace(":9", ":7")).unwrap()[0].transform, 7);
+    }
+
+    #[test]
+    fn parse_hyprctl_mirrored_fixture() {
+        let monitors = Hyprctl::parse(include_str!("../../tests/fixtures/hyprctl_monitors_mirrored.json")).unwrap();
+        assert_eq!(monitors.len(), 3);
+
+        assert_eq!(monitors[1].mirror_of.as_deref(), Some("eDP-1"));
+
+        let disabled = &monitors[2];
+        assert!(!disabled.enabled);
+        assert!(disabled.get_current_resolution().is_none());
+        assert!(disabled.active_workspace.is_none());
+        assert_eq!(disabled.modes.len(), 2);
+    }
+}
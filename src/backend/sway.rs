@@ -0,0 +1,307 @@
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use serde::Deserialize;
+use crate::monitor::{Monitor, Position, Resolution, Workspace, TRANSFORM_NAMES};
+use super::DisplayBackend;
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+pub const RUN_COMMAND: u32 = 0;
+pub const GET_OUTPUTS: u32 = 3;
+
+#[derive(Debug)]
+pub struct Sway {
+    socket: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SwayOutput {
+    name: String,
+    make: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+    #[serde(default)]
+    active: bool,
+    #[serde(default)]
+    power: Option<bool>,
+    #[serde(default)]
+    scale: Option<f32>,
+    #[serde(default)]
+    transform: Option<String>,
+    #[serde(default)]
+    adaptive_sync_status: Option<String>,
+    #[serde(default)]
+    current_workspace: Option<String>,
+    #[serde(default)]
+    focused: bool,
+    #[serde(default)]
+    modes: Vec<SwayMode>,
+    current_mode: Option<SwayMode>,
+    rect: Option<SwayRect>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct SwayMode {
+    width: i32,
+    height: i32,
+    // in mHz
+    refresh: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SwayRect {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandReply {
+    success: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl Sway {
+    pub fn new(socket: Option<PathBuf>) -> Self {
+        Sway { socket }
+    }
+
+    // sway exports the path of its IPC socket to the processes it spawns
+    pub fn from_env() -> Self {
+        Sway::new(std::env::var_os("SWAYSOCK").map(PathBuf::from))
+    }
+
+    pub fn encode(kind: u32, payload: &str) -> Vec<u8> {
+        let mut message = MAGIC.to_vec();
+        message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.extend_from_slice(payload.as_bytes());
+        message
+    }
+
+    pub fn decode(reader: &mut impl Read) -> io::Result<(u32, String)> {
+        let mut header = [0u8; 14];
+        reader.read_exact(&mut header)?;
+        if &header[..6] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid sway IPC header"));
+        }
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]);
+        let kind = u32::from_ne_bytes([header[10], header[11], header[12], header[13]]);
+        let mut payload = vec![0u8; length as usize];
+        reader.read_exact(&mut payload)?;
+        let payload = String::from_utf8(payload)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok((kind, payload))
+    }
+
+    fn request(&self, kind: u32, payload: &str) -> io::Result<String> {
+        let Some(socket) = &self.socket else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "SWAYSOCK is not set, is sway running ?"));
+        };
+        let mut stream = UnixStream::connect(socket)
+            .map_err(|e| io::Error::new(e.kind(), format!("Failed to connect to {}: {}", socket.display(), e)))?;
+        stream.write_all(&Sway::encode(kind, payload))?;
+        let (reply_kind, reply) = Sway::decode(&mut stream)?;
+        if reply_kind != kind {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unexpected sway IPC reply type {}", reply_kind),
+            ));
+        }
+        Ok(reply)
+    }
+
+    pub fn parse(json: &str) -> io::Result<Vec<Monitor>> {
+        let outputs: Vec<SwayOutput> = serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Deserialization error: {}", e)))?;
+        Ok(outputs.into_iter().map(Monitor::from).collect())
+    }
+
+    pub fn commands(monitors: &[Monitor]) -> Vec<String> {
+        monitors
+            .iter()
+            .map(|monitor| format!("output {} {}", monitor.name, Sway::output_settings(monitor)))
+            .collect()
+    }
+
+    // the settings of an output command, shared with the generated config file
+    pub fn output_settings(monitor: &Monitor) -> String {
+        if !monitor.enabled {
+            return "disable".to_string();
+        }
+        let mut settings = vec!["enable".to_string()];
+        if let Some(mode) = monitor.get_current_resolution().or(monitor.get_prefered_resolution()) {
+            settings.push(format!("mode {}x{}@{:.3}Hz", mode.width, mode.height, mode.refresh));
+        }
+        if let Some(position) = &monitor.position {
+            settings.push(format!("pos {} {}", position.x, position.y));
+        }
+        settings.push(format!("scale {}", monitor.scale.unwrap_or(1.0)));
+        settings.push(format!("transform {}", monitor.transform_name()));
+        settings.push(format!("adaptive_sync {}", if monitor.vrr { "on" } else { "off" }));
+        settings.join(" ")
+    }
+}
+
+impl From<SwayOutput> for Monitor {
+    fn from(output: SwayOutput) -> Self {
+        let mut modes: Vec<Resolution> = output.modes
+            .iter()
+            .map(|mode| Resolution {
+                width: mode.width,
+                height: mode.height,
+                refresh: mode.refresh as f32 / 1000.0,
+                preferred: false,
+                current: false,
+            })
+            .collect();
+        // sway does not report the preferred mode, the first one is the native mode
+        if let Some(first) = modes.first_mut() {
+            first.preferred = true;
+        }
+        if output.active
+            && let Some(current) = &output.current_mode {
+            match output.modes.iter().position(|mode| mode == current) {
+                Some(index) => modes[index].current = true,
+                None => modes.push(Resolution {
+                    width: current.width,
+                    height: current.height,
+                    refresh: current.refresh as f32 / 1000.0,
+                    preferred: false,
+                    current: true,
+                }),
+            }
+        }
+        let transform = output.transform
+            .and_then(|name| TRANSFORM_NAMES.iter().position(|transform| *transform == name))
+            .unwrap_or(0) as u8;
+        Monitor {
+            description: Some(
+                [&output.make, &output.model, &output.serial]
+                    .iter()
+                    .filter_map(|part| part.as_deref())
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+            ),
+            name: output.name,
+            make: output.make,
+            model: output.model,
+            serial: output.serial,
+            enabled: output.active,
+            modes,
+            position: output.rect
+                .filter(|_| output.active)
+                .map(|rect| Position { x: rect.x, y: rect.y }),
+            // disabled outputs report a scale of -1
+            scale: output.scale.filter(|scale| *scale > 0.0),
+            transform,
+            vrr: output.adaptive_sync_status.as_deref() == Some("enabled"),
+            active_workspace: output.current_workspace.map(|name| Workspace {
+                id: name.parse().unwrap_or_default(),
+                name,
+            }),
+            focused: output.focused,
+            dpms_status: output.power,
+            ..Default::default()
+        }
+    }
+}
+
+impl DisplayBackend for Sway {
+    fn get_monitors(&mut self) -> io::Result<Vec<Monitor>> {
+        let reply = self.request(GET_OUTPUTS, "")?;
+        Sway::parse(&reply)
+    }
+
+    fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()> {
+        let reply = self.request(RUN_COMMAND, &Sway::commands(monitors).join("; "))?;
+        let replies: Vec<CommandReply> = serde_json::from_str(&reply)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Deserialization error: {}", e)))?;
+        match replies.into_iter().find(|reply| !reply.success) {
+            Some(reply) => Err(io::Error::other(format!(
+                "sway rejected the layout: {}",
+                reply.error.unwrap_or_default()
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn parse_sway_outputs_fixture() {
+        let monitors = Sway::parse(include_str!("../../tests/fixtures/sway_outputs.json")).unwrap();
+        assert_eq!(monitors.len(), 3);
+
+        let laptop = &monitors[0];
+        assert_eq!(laptop.name, "eDP-1");
+        assert_eq!(laptop.identity(), "BOE 0x0BCA Unknown");
+        assert!(laptop.modes[0].preferred && laptop.modes[0].current);
+        assert_eq!(laptop.modes[0].refresh, 59.999);
+        assert_eq!(laptop.scale, Some(1.5));
+        assert_eq!(laptop.active_workspace.as_ref().unwrap().id, 1);
+
+        let external = &monitors[1];
+        assert_eq!(external.position, Some(Position { x: 1504, y: 0 }));
+        assert_eq!(external.get_current_resolution().unwrap().width, 2560);
+        assert_eq!(external.transform, 1);
+        assert!(external.vrr);
+        assert!(external.focused);
+
+        let projector = &monitors[2];
+        assert!(!projector.enabled);
+        assert_eq!(projector.position, None);
+        assert_eq!(projector.scale, None);
+        assert!(projector.get_current_resolution().is_none());
+    }
+
+    #[test]
+    fn sway_output_commands() {
+        let mut monitors = test_monitors();
+        monitors[0].transform = 5;
+        assert_eq!(Sway::commands(&monitors), vec![
+            "output Monitor 1 enable mode 1920x1080@60.000Hz pos 0 0 scale 1 transform flipped-90 adaptive_sync off",
+            "output Monitor 2 disable",
+        ]);
+    }
+
+    #[test]
+    fn sway_ipc_round_trip() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-sway-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        // a stub answering one get_outputs and one run_command request
+        let server = thread::spawn(move || -> io::Result<Vec<(u32, String)>> {
+            let mut requests = vec![];
+            for reply in [include_str!("../../tests/fixtures/sway_outputs.json"), r#"[{"success": true}, {"success": false, "error": "Unknown output"}]"#] {
+                let (mut stream, _) = listener.accept()?;
+                let (kind, payload) = Sway::decode(&mut stream)?;
+                stream.write_all(&Sway::encode(kind, reply))?;
+                requests.push((kind, payload));
+            }
+            Ok(requests)
+        });
+
+        let mut sway = Sway::new(Some(path.clone()));
+        let monitors = sway.get_monitors()?;
+        assert_eq!(monitors.len(), 3);
+        let error = sway.apply(&monitors[..2]).unwrap_err();
+        assert_eq!(error.to_string(), "sway rejected the layout: Unknown output");
+
+        let requests = server.join().unwrap()?;
+        assert_eq!(requests[0], (GET_OUTPUTS, String::new()));
+        assert_eq!(requests[1].0, RUN_COMMAND);
+        assert!(requests[1].1.starts_with("output eDP-1 enable mode 2256x1504@59.999Hz pos 0 0 scale 1.5"));
+        assert!(requests[1].1.contains("; output DP-3 enable"));
+
+        std::fs::remove_file(&path)?;
+        assert!(Sway::new(None).get_monitors().is_err());
+        Ok(())
+    }
+}
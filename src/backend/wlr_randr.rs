@@ -0,0 +1,114 @@
+use std::io;
+use crate::monitor::Monitor;
+use super::{CommandRunner, DisplayBackend};
+
+#[derive(Debug)]
+pub struct WlrRandr {
+    runner: Box<dyn CommandRunner>,
+}
+
+impl WlrRandr {
+    pub fn new(runner: Box<dyn CommandRunner>) -> Self {
+        WlrRandr { runner }
+    }
+
+    pub fn parse(json: &str) -> io::Result<Vec<Monitor>> {
+        serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Deserialization error: {}", e)))
+    }
+
+    pub fn apply_args(monitors: &[Monitor]) -> Vec<String> {
+        let mut args = vec![];
+        for monitor in monitors {
+            args.push("--output".to_string());
+            args.push(monitor.name.clone());
+            if !monitor.enabled {
+                args.push("--off".to_string());
+                continue;
+            }
+            args.push("--on".to_string());
+            if let Some(mode) = monitor.get_current_resolution().or(monitor.get_prefered_resolution()) {
+                args.push("--mode".to_string());
+                args.push(format!("{}x{}@{}Hz", mode.width, mode.height, mode.refresh));
+            }
+            if let Some(position) = &monitor.position {
+                args.push("--pos".to_string());
+                args.push(format!("{},{}", position.x, position.y));
+            }
+            args.push("--scale".to_string());
+            args.push(monitor.scale.unwrap_or(1.0).to_string());
+            args.push("--transform".to_string());
+            args.push(monitor.transform_name().to_string());
+        }
+        args
+    }
+
+    // quotes an argument for a POSIX shell when it is not a plain word
+    pub fn quote(arg: &str) -> String {
+        if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_.,@:/".contains(c)) {
+            return arg.to_string();
+        }
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+
+    // the exact command run to apply the layout, as it would be typed in a shell
+    pub fn command_line(monitors: &[Monitor]) -> String {
+        let mut line = "wlr-randr".to_string();
+        for arg in WlrRandr::apply_args(monitors) {
+            line.push(' ');
+            line.push_str(&WlrRandr::quote(&arg));
+        }
+        line
+    }
+}
+
+impl DisplayBackend for WlrRandr {
+    fn get_monitors(&mut self) -> io::Result<Vec<Monitor>> {
+        let stdout = self.runner.run("wlr-randr", &["--json".to_string()])?;
+        WlrRandr::parse(&stdout)
+    }
+
+    fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()> {
+        self.runner.run("wlr-randr", &WlrRandr::apply_args(monitors)).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn wlr_randr_apply_args() {
+        let args = WlrRandr::apply_args(&test_monitors());
+        assert_eq!(
+            args.join(" "),
+            "--output Monitor 1 --on --mode 1920x1080@60Hz --pos 0,0 --scale 1 --transform normal --output Monitor 2 --off"
+        );
+    }
+
+    #[test]
+    fn wlr_randr_command_line() {
+        let mut monitors = test_monitors();
+        monitors[1].name = "HDMI-A-1".to_string();
+        monitors[0].name = "Bob's TV".to_string();
+        assert_eq!(
+            WlrRandr::command_line(&monitors),
+            "wlr-randr --output 'Bob'\\''s TV' --on --mode 1920x1080@60Hz --pos 0,0 --scale 1 --transform normal --output HDMI-A-1 --off"
+        );
+        assert_eq!(WlrRandr::quote(""), "''");
+    }
+
+    #[test]
+    fn parse_wlr_randr_transform() {
+        let json = r#"[{
+            "name": "DP-3", "description": "Dell Inc. DELL U2720Q 8LXMZ13", "make": "Dell Inc.",
+            "model": "DELL U2720Q", "serial": "8LXMZ13", "physical_size": {"width": 600, "height": 340},
+            "enabled": true, "modes": [{"width": 2560, "height": 1440, "refresh": 59.95, "preferred": true, "current": true}],
+            "position": {"x": 0, "y": 0}, "transform": "flipped-90", "scale": 1.0, "adaptive_sync": true
+        }]"#;
+        let monitors = WlrRandr::parse(json).unwrap();
+        assert_eq!(monitors[0].transform, 5);
+        assert!(monitors[0].vrr);
+    }
+}
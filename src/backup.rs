@@ -0,0 +1,199 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BACKUPS: usize = 5;
+const SUFFIX: &str = ".bak";
+
+// the file a symlink points to (dotfiles are often linked), so the link itself is not replaced
+fn resolve(path: &Path) -> PathBuf {
+    match fs::canonicalize(path) {
+        Ok(resolved) => resolved,
+        // a dangling link still names the file to create
+        Err(_) => match fs::read_link(path) {
+            Ok(target) => path.parent().map_or(target.clone(), |dir| dir.join(&target)),
+            Err(_) => path.to_path_buf(),
+        },
+    }
+}
+
+// write to a temporary file next to the target and rename it, so the target is never half written
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let path = &resolve(path);
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid path {}", path.display())))?
+        .to_string_lossy();
+    let temp = path.with_file_name(format!(".{}.tmp-{}", name, std::process::id()));
+    let result = fs::File::create(&temp).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        // the new file keeps the mode of the one it replaces
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.sync_all()
+    });
+    match result.and_then(|_| fs::rename(&temp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+// UTC date of the backup, sortable as text
+fn timestamp() -> String {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let (days, time) = (seconds / 86400, seconds % 86400);
+    // civil date from the days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}.{:03}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+fn prefix(path: &Path) -> String {
+    format!("{}.", path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default())
+}
+
+// backups of the file, newest first
+pub fn list(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let Some(dir) = path.parent() else {
+        return Ok(vec![]);
+    };
+    let prefix = prefix(path);
+    let mut backups = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|backup| {
+                let name = backup.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                name.starts_with(&prefix) && name.ends_with(SUFFIX)
+            })
+            .collect::<Vec<PathBuf>>(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e),
+    };
+    backups.sort();
+    backups.reverse();
+    Ok(backups)
+}
+
+// date of the backup as "YYYY-MM-DD HH:MM:SS" (UTC)
+pub fn label(path: &Path, backup: &Path) -> String {
+    let name = backup.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let stamp = name
+        .strip_prefix(&prefix(path))
+        .and_then(|rest| rest.strip_suffix(SUFFIX))
+        .unwrap_or(&name);
+    match (stamp.get(0..4), stamp.get(4..6), stamp.get(6..8), stamp.get(9..11), stamp.get(11..13), stamp.get(13..15)) {
+        (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) => {
+            format!("{}-{}-{} {}:{}:{}", year, month, day, hour, minute, second)
+        },
+        _ => stamp.to_string(),
+    }
+}
+
+// copy the current file aside before it gets the new content, and only keep the newest ones
+pub fn create(path: &Path, content: &str, keep: usize) -> io::Result<Option<PathBuf>> {
+    if keep == 0 || !path.exists() {
+        return Ok(None);
+    }
+    // an unchanged file would push the useful backups out
+    if fs::read_to_string(path)? == content {
+        return Ok(None);
+    }
+    let backup = path.with_file_name(format!("{}{}{}", prefix(path), timestamp(), SUFFIX));
+    fs::copy(path, &backup)?;
+    for old in list(path)?.into_iter().skip(keep) {
+        fs::remove_file(old)?;
+    }
+    Ok(Some(backup))
+}
+
+pub fn restore(path: &Path, backup: &Path, keep: usize) -> io::Result<()> {
+    let content = fs::read_to_string(backup)?;
+    create(path, &content, keep)?;
+    write_atomic(path, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_rotate_backups() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("display-tui-backup-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("monitors.conf");
+
+        assert_eq!(create(&path, "version 1\n", 2)?, None);
+        for version in 1..=4 {
+            let content = format!("version {}\n", version);
+            create(&path, &content, 2)?;
+            write_atomic(&path, &content)?;
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        // saving the same content again keeps the backups
+        assert_eq!(create(&path, "version 4\n", 2)?, None);
+        assert_eq!(fs::read_to_string(&path)?, "version 4\n");
+
+        let backups = list(&path)?;
+        assert_eq!(backups.len(), 2);
+        assert_eq!(fs::read_to_string(&backups[0])?, "version 3\n");
+        assert_eq!(fs::read_to_string(&backups[1])?, "version 2\n");
+        // no temporary file left behind
+        assert_eq!(fs::read_dir(&dir)?.count(), 3);
+
+        let name = backups[0].file_name().unwrap().to_string_lossy().to_string();
+        let stamp = &name["monitors.conf.".len()..];
+        assert_eq!(
+            label(&path, &backups[0]),
+            format!("{}-{}-{} {}:{}:{}", &stamp[0..4], &stamp[4..6], &stamp[6..8], &stamp[9..11], &stamp[11..13], &stamp[13..15])
+        );
+        assert_eq!(label(&path, &dir.join("monitors.conf.old.bak")), "old");
+
+        restore(&path, &backups[1], 2)?;
+        assert_eq!(fs::read_to_string(&path)?, "version 2\n");
+        assert_eq!(fs::read_to_string(&list(&path)?[0])?, "version 4\n");
+
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn write_through_symlinks() -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("display-tui-link-{}", std::process::id()));
+        fs::create_dir_all(dir.join("dotfiles"))?;
+        let target = dir.join("dotfiles/monitors.conf");
+        let link = dir.join("monitors.conf");
+        fs::write(&target, "old\n")?;
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600))?;
+        std::os::unix::fs::symlink(&target, &link)?;
+
+        write_atomic(&link, "new\n")?;
+        assert!(link.is_symlink());
+        assert_eq!(fs::read_to_string(&target)?, "new\n");
+        assert_eq!(fs::metadata(&target)?.permissions().mode() & 0o777, 0o600);
+
+        fs::remove_dir_all(&dir)
+    }
+}
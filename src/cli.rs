@@ -0,0 +1,608 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::process::ExitCode;
+use crate::backend::{CommandRunner, DisplayBackend, SystemRunner, WlrRandr};
+use crate::configuration::Configuration;
+use crate::diff;
+use crate::error::Error;
+use crate::kanshi;
+use crate::layout;
+use crate::monitor::{Alignment, Monitor, Placement, Position};
+use crate::profile;
+use crate::writer::{self, ConfigFormat};
+
+pub const USAGE: &str = "Usage:
+  display-tui                      open the TUI
+  display-tui list [--json]        list the displays
+  display-tui set <output> [--mode <width>x<height>[@<refresh>]] [--pos <x>x<y>] [--scale <scale>]
+                   [--left-of|--right-of|--above|--below <output> [--align top|center|bottom|left|right]]
+  display-tui enable <output>      enable a display
+  display-tui disable <output>     disable a display
+  display-tui save [--dry-run]     save the current layout to monitors_config_path, or only print the diff
+  display-tui kanshi [<profile>] [--dry-run]
+                                   export the current layout or a saved profile to the kanshi config
+  display-tui apply [<profile>] [--dry-run]
+                                   apply the saved layout or a saved profile with wlr-randr, or only print the command
+  display-tui daemon               apply the best matching profile on hotplug";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Tui,
+    Help,
+    Daemon,
+    List { json: bool },
+    Set {
+        output: String,
+        mode: Option<String>,
+        position: Option<String>,
+        scale: Option<String>,
+        relative: Option<(Placement, String)>,
+        align: Option<Alignment>,
+    },
+    Enable(String),
+    Disable(String),
+    Save { dry_run: bool },
+    Kanshi { profile: Option<String>, dry_run: bool },
+    Apply { profile: Option<String>, dry_run: bool },
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    Validation(String),
+    Backend(io::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Validation(_) => 2,
+            CliError::Backend(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Validation(message) => write!(f, "{}", message),
+            CliError::Backend(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::Backend(e)
+    }
+}
+
+// an invalid layout or config file is a validation error, not a backend one
+impl From<Error> for CliError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Backend(e) | Error::Io(e) => CliError::Backend(e),
+            e @ (Error::Config { .. } | Error::MissingMode(_) | Error::MissingPosition(_)) => {
+                CliError::Validation(e.to_string())
+            },
+        }
+    }
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Command, CliError> {
+        let Some(name) = args.first() else {
+            return Ok(Command::Tui);
+        };
+        let rest = &args[1..];
+        match name.as_str() {
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            "daemon" => Ok(Command::Daemon),
+            "list" => match rest {
+                [] => Ok(Command::List { json: false }),
+                [flag] if flag == "--json" => Ok(Command::List { json: true }),
+                _ => Err(CliError::Validation(format!("Unexpected arguments for list: {}", rest.join(" ")))),
+            },
+            "enable" => Ok(Command::Enable(Command::single_output(name, rest)?)),
+            "disable" => Ok(Command::Disable(Command::single_output(name, rest)?)),
+            "save" => match rest {
+                [] => Ok(Command::Save { dry_run: false }),
+                [flag] if flag == "--dry-run" => Ok(Command::Save { dry_run: true }),
+                _ => Err(CliError::Validation(format!("Unexpected arguments for save: {}", rest.join(" ")))),
+            },
+            "set" => Command::parse_set(rest),
+            "kanshi" => {
+                let (profile, dry_run) = Command::parse_profile(name, rest)?;
+                Ok(Command::Kanshi { profile, dry_run })
+            },
+            "apply" => {
+                let (profile, dry_run) = Command::parse_profile(name, rest)?;
+                Ok(Command::Apply { profile, dry_run })
+            },
+            _ => Err(CliError::Validation(format!("Unknown command {}\n{}", args.join(" "), USAGE))),
+        }
+    }
+
+    fn single_output(name: &str, rest: &[String]) -> Result<String, CliError> {
+        match rest {
+            [output] => Ok(output.clone()),
+            _ => Err(CliError::Validation(format!("{} expects exactly one output", name))),
+        }
+    }
+
+    fn parse_profile(name: &str, rest: &[String]) -> Result<(Option<String>, bool), CliError> {
+        let (mut profile, mut dry_run) = (None, false);
+        for arg in rest {
+            match arg.as_str() {
+                "--dry-run" => dry_run = true,
+                option if option.starts_with("--") => {
+                    return Err(CliError::Validation(format!("Unknown option {}", option)));
+                },
+                _ if profile.is_none() => profile = Some(arg.clone()),
+                _ => return Err(CliError::Validation(format!("{} expects at most one profile", name))),
+            }
+        }
+        Ok((profile, dry_run))
+    }
+
+    fn parse_set(rest: &[String]) -> Result<Command, CliError> {
+        let Some((output, options)) = rest.split_first() else {
+            return Err(CliError::Validation("set expects an output".to_string()));
+        };
+        let (mut mode, mut position, mut scale) = (None, None, None);
+        let (mut relative, mut align) = (None, None);
+        let mut options = options.iter();
+        while let Some(option) = options.next() {
+            let Some(value) = options.next() else {
+                return Err(CliError::Validation(format!("{} expects a value", option)));
+            };
+            if let Some(placement) = option.strip_prefix("--").and_then(Placement::parse) {
+                relative = Some((placement, value.clone()));
+                continue;
+            }
+            match option.as_str() {
+                "--mode" => mode = Some(value.clone()),
+                "--pos" => position = Some(value.clone()),
+                "--scale" => scale = Some(value.clone()),
+                "--align" => align = Some(Alignment::parse(value)
+                    .ok_or_else(|| CliError::Validation(format!("Invalid alignment {}", value)))?),
+                _ => return Err(CliError::Validation(format!("Unknown option {}", option))),
+            }
+        }
+        if position.is_some() && relative.is_some() {
+            return Err(CliError::Validation("--pos cannot be combined with a relative placement".to_string()));
+        }
+        if align.is_some() && relative.is_none() {
+            return Err(CliError::Validation("--align expects a relative placement".to_string()));
+        }
+        Ok(Command::Set {
+            output: output.clone(),
+            mode,
+            position,
+            scale,
+            relative,
+            align,
+        })
+    }
+}
+
+pub fn run(command: Command) -> ExitCode {
+    let config = match Configuration::get() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut backend = config.backend.create();
+    match execute(command, backend.as_mut(), &mut SystemRunner, &config, &mut io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+pub fn execute(
+    command: Command,
+    backend: &mut dyn DisplayBackend,
+    runner: &mut dyn CommandRunner,
+    config: &Configuration,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    match &command {
+        Command::Help => writeln!(out, "{}", USAGE)?,
+        Command::List { json } => {
+            let monitors = backend.get_monitors()?;
+            if *json {
+                let content = serde_json::to_string_pretty(&monitors)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                writeln!(out, "{}", content)?;
+            } else {
+                for monitor in &monitors {
+                    writeln!(out, "{}", list_line(monitor))?;
+                }
+            }
+        },
+        Command::Set { output, mode, position, scale, relative, align } => {
+            let mut monitors = backend.get_monitors()?;
+            let monitor = find_output(&mut monitors, output)?;
+            if let Some(mode) = mode {
+                let index = find_mode(monitor, mode)?;
+                monitor.set_current_resolution(index);
+            }
+            if let Some(position) = position {
+                let (x, y) = parse_position(position)?;
+                match &monitor.position {
+                    Some(current) => {
+                        let (dx, dy) = (x - current.x, y - current.y);
+                        monitor.move_horizontal(dx);
+                        monitor.move_vertical(dy);
+                    },
+                    None => monitor.position = Some(Position { x, y }),
+                }
+            }
+            if let Some(scale) = scale {
+                monitor.scale = Some(parse_scale(scale)?);
+            }
+            if let Some((placement, other)) = relative {
+                let index = find_index(&monitors, output)?;
+                let reference = find_index(&monitors, other)?;
+                Monitor::place_relative(&mut monitors, index, reference, *placement, align.unwrap_or_default())
+                    .map_err(CliError::Validation)?;
+            }
+            backend.apply(&monitors)?;
+        },
+        Command::Enable(output) | Command::Disable(output) => {
+            let enabled = matches!(command, Command::Enable(_));
+            let mut monitors = backend.get_monitors()?;
+            find_output(&mut monitors, output)?.enabled = enabled;
+            backend.apply(&monitors)?;
+        },
+        Command::Save { dry_run } => {
+            let mut monitors = backend.get_monitors()?;
+            // the live layout is saved with the options only the config file knows about
+            let mut merged = monitors.clone();
+            config.merge_monitors_config(&mut merged)?;
+            for (monitor, merged) in monitors.iter_mut().zip(merged) {
+                monitor.extra_options = merged.extra_options;
+            }
+            for issue in layout::validate(&monitors) {
+                eprintln!("Warning: {}", issue.message(&monitors));
+            }
+            if *dry_run {
+                for line in writer::diff(config, &monitors)? {
+                    writeln!(out, "{}", line.text())?;
+                }
+            } else {
+                writer::save(config, &monitors)?;
+            }
+        },
+        Command::Kanshi { profile, dry_run } => {
+            let (name, settings) = match profile {
+                Some(name) => match config.profiles.get(name) {
+                    Some(settings) => (name.clone(), settings.clone()),
+                    None => return Err(CliError::Validation(format!("Unknown profile {}", name))),
+                },
+                None => {
+                    let monitors = backend.get_monitors()?;
+                    (kanshi::layout_name(&monitors), profile::from_monitors(&monitors))
+                },
+            };
+            let path = config.targets
+                .iter()
+                .find(|target| target.format == ConfigFormat::Kanshi)
+                .map_or(kanshi::DEFAULT_CONFIG_PATH, |target| target.path.as_str());
+            if *dry_run {
+                let (existing, rendered) = kanshi::preview(path, &name, &settings)?;
+                for line in diff::unified(&existing, &rendered, diff::CONTEXT) {
+                    writeln!(out, "{}", line.text())?;
+                }
+            } else {
+                kanshi::save(path, &name, &settings, config.backups)?;
+            }
+        },
+        Command::Apply { profile, dry_run } => {
+            let mut monitors = backend.get_monitors()?;
+            match profile {
+                Some(name) => match config.profiles.get(name) {
+                    Some(settings) => {
+                        profile::load(settings, &mut monitors);
+                    },
+                    None => return Err(CliError::Validation(format!("Unknown profile {}", name))),
+                },
+                None => config.merge_monitors_config(&mut monitors)?,
+            }
+            // wlr-randr is run whatever the backend, without reloading the compositor config
+            if *dry_run {
+                writeln!(out, "{}", WlrRandr::command_line(&monitors))?;
+            } else {
+                runner.run("wlr-randr", &WlrRandr::apply_args(&monitors))?;
+            }
+        },
+        Command::Tui | Command::Daemon => {},
+    }
+    Ok(())
+}
+
+fn list_line(monitor: &Monitor) -> String {
+    let resolution = match monitor.get_current_resolution().or(monitor.get_prefered_resolution()) {
+        Some(mode) => format!("{}x{}@{}", mode.width, mode.height, mode.refresh),
+        None => "N/A".to_string(),
+    };
+    let position = match &monitor.position {
+        Some(pos) => format!("{}x{}", pos.x, pos.y),
+        None => "N/A".to_string(),
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        monitor.name,
+        if monitor.enabled { "enabled" } else { "disabled" },
+        resolution,
+        position,
+        monitor.scale.unwrap_or(1.0),
+        monitor.description.as_deref().unwrap_or(""),
+    )
+}
+
+fn find_output<'a>(monitors: &'a mut [Monitor], output: &str) -> Result<&'a mut Monitor, CliError> {
+    let index = find_index(monitors, output)?;
+    Ok(&mut monitors[index])
+}
+
+fn find_index(monitors: &[Monitor], output: &str) -> Result<usize, CliError> {
+    monitors
+        .iter()
+        .position(|monitor| monitor.name == output || monitor.identity() == output)
+        .ok_or_else(|| CliError::Validation(format!("Unknown output {}", output)))
+}
+
+fn find_mode(monitor: &Monitor, mode: &str) -> Result<usize, CliError> {
+    let invalid = || CliError::Validation(format!("Invalid mode {}, expected <width>x<height>[@<refresh>]", mode));
+    let (size, refresh) = match mode.trim_end_matches("Hz").split_once('@') {
+        Some((size, refresh)) => (size, Some(refresh.parse::<f32>().map_err(|_| invalid())?)),
+        None => (mode, None),
+    };
+    let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+    let width: i32 = width.parse().map_err(|_| invalid())?;
+    let height: i32 = height.parse().map_err(|_| invalid())?;
+    monitor.modes
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.width == width && m.height == height)
+        .filter(|(_, m)| refresh.is_none_or(|refresh| (m.refresh - refresh).abs() < 0.5))
+        .max_by(|(_, a), (_, b)| a.refresh.total_cmp(&b.refresh))
+        .map(|(index, _)| index)
+        .ok_or_else(|| CliError::Validation(format!("{} does not support mode {}", monitor.name, mode)))
+}
+
+fn parse_position(position: &str) -> Result<(i32, i32), CliError> {
+    let invalid = || CliError::Validation(format!("Invalid position {}, expected <x>x<y>", position));
+    let (x, y) = position
+        .split_once('x')
+        .or_else(|| position.split_once(','))
+        .ok_or_else(invalid)?;
+    Ok((x.parse().map_err(|_| invalid())?, y.parse().map_err(|_| invalid())?))
+}
+
+fn parse_scale(scale: &str) -> Result<f32, CliError> {
+    match scale.parse::<f32>() {
+        Ok(value) if value > 0.0 => Ok(value),
+        _ => Err(CliError::Validation(format!("Invalid scale {}", scale))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::fake::{FakeBackend, FakeRunner};
+    use crate::test_utils::tests::test_monitors;
+    use crate::writer::Target;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(Command::parse(&[]).unwrap(), Command::Tui);
+        assert_eq!(Command::parse(&args("list --json")).unwrap(), Command::List { json: true });
+        assert_eq!(
+            Command::parse(&args("set DP-1 --mode 2560x1440@144 --pos 1920x0 --scale 1.25")).unwrap(),
+            Command::Set {
+                output: "DP-1".to_string(),
+                mode: Some("2560x1440@144".to_string()),
+                position: Some("1920x0".to_string()),
+                scale: Some("1.25".to_string()),
+                relative: None,
+                align: None,
+            }
+        );
+        assert_eq!(
+            Command::parse(&args("set DP-1 --right-of eDP-1 --align center")).unwrap(),
+            Command::Set {
+                output: "DP-1".to_string(),
+                mode: None,
+                position: None,
+                scale: None,
+                relative: Some((Placement::RightOf, "eDP-1".to_string())),
+                align: Some(Alignment::Center),
+            }
+        );
+        assert_eq!(Command::parse(&args("set DP-1 --pos 0x0 --above eDP-1")).unwrap_err().exit_code(), 2);
+        assert_eq!(Command::parse(&args("set DP-1 --align middle")).unwrap_err().exit_code(), 2);
+        assert_eq!(Command::parse(&args("set DP-1 --scale")).unwrap_err().exit_code(), 2);
+        assert_eq!(Command::parse(&args("enable")).unwrap_err().exit_code(), 2);
+        assert_eq!(Command::parse(&args("save --dry-run")).unwrap(), Command::Save { dry_run: true });
+        assert_eq!(Command::parse(&args("save --force")).unwrap_err().exit_code(), 2);
+        assert_eq!(Command::parse(&args("frobnicate")).unwrap_err().exit_code(), 2);
+
+        // layout and config errors are validation errors
+        assert_eq!(CliError::from(Error::MissingMode("DP-1".to_string())).exit_code(), 2);
+        assert_eq!(CliError::from(Error::Config { path: "config.json".to_string(), message: "bad".to_string() }).exit_code(), 2);
+        assert_eq!(CliError::from(Error::Backend(io::Error::other("hyprctl not found"))).exit_code(), 1);
+    }
+
+    #[test]
+    fn execute_commands() {
+        let config = Configuration::default();
+        let mut backend = FakeBackend::new(test_monitors());
+        let mut runner = FakeRunner::default();
+        let mut out = vec![];
+
+        let command = Command::parse(&args("set Monitor_1 --scale 2")).unwrap();
+        assert_eq!(execute(command, &mut backend, &mut runner, &config, &mut out).unwrap_err().exit_code(), 2);
+
+        let command = Command::Set {
+            output: "Monitor 2".to_string(),
+            mode: Some("1920x1080".to_string()),
+            position: Some("1920x-200".to_string()),
+            scale: Some("1.5".to_string()),
+            relative: None,
+            align: None,
+        };
+        execute(command, &mut backend, &mut runner, &config, &mut out).unwrap();
+        let monitor = &backend.monitors[1];
+        assert!(monitor.modes[0].current);
+        assert_eq!(monitor.position, Some(Position { x: 1920, y: -200 }));
+        assert_eq!(monitor.scale, Some(1.5));
+
+        let command = Command::Enable("Some Description 2".to_string());
+        execute(command, &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert!(backend.monitors[1].enabled);
+
+        let command = Command::Set {
+            output: "Monitor 2".to_string(),
+            mode: None,
+            position: None,
+            scale: None,
+            relative: Some((Placement::LeftOf, "Monitor 1".to_string())),
+            align: Some(Alignment::End),
+        };
+        execute(command, &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert_eq!(backend.monitors[1].position, Some(Position { x: -1280, y: 360 }));
+        let command = Command::Set {
+            output: "Monitor 2".to_string(),
+            mode: Some("1920x1080".to_string()),
+            position: Some("1920x-200".to_string()),
+            scale: None,
+            relative: None,
+            align: None,
+        };
+        execute(command, &mut backend, &mut runner, &config, &mut out).unwrap();
+
+        let command = Command::parse(&args("set Monitor_2")).unwrap();
+        assert!(matches!(execute(command, &mut backend, &mut runner, &config, &mut out), Err(CliError::Validation(_))));
+
+        execute(Command::List { json: false }, &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Monitor 1\tenabled\t1920x1080@60\t0x0\t1\tDescription 1\nMonitor 2\tenabled\t1920x1080@60\t1920x-200\t1.5\tDescription 2\n"
+        );
+    }
+
+    #[test]
+    fn save_dry_run() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-dry-run-{}.conf", std::process::id()));
+        let existing = "# layout\nmonitor = desc:Some Description 1, 1920x1080@60, 0x0, 1, bitdepth, 10\n";
+        std::fs::write(&path, existing)?;
+        let config = Configuration {
+            monitors_config_path: path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let mut backend = FakeBackend::new(test_monitors());
+        let mut runner = FakeRunner::default();
+        backend.monitors[0].position = Some(Position { x: 1280, y: 0 });
+        let mut out = vec![];
+
+        execute(Command::Save { dry_run: true }, &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "@@ -1,2 +1,3 @@\n # layout\n\
+            -monitor = desc:Some Description 1, 1920x1080@60, 0x0, 1, bitdepth, 10\n\
+            +monitor = desc:Some Description 1, 1920x1080@60, 1280x0, 1, bitdepth, 10\n\
+            +monitor = Monitor 2, disabled\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path)?, existing);
+
+        std::fs::remove_file(&path)
+    }
+
+    #[test]
+    fn export_kanshi_profile() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-kanshi-{}", std::process::id()));
+        std::fs::write(&path, "profile other {\n\toutput eDP-1 enable\n}\n")?;
+        let mut config = Configuration {
+            targets: vec![Target { format: ConfigFormat::Kanshi, path: path.to_string_lossy().to_string() }],
+            ..Default::default()
+        };
+        config.profiles.insert("desk".to_string(), profile::from_monitors(&test_monitors()[..1]));
+        let mut backend = FakeBackend::new(test_monitors());
+        let mut runner = FakeRunner::default();
+        let mut out = vec![];
+
+        assert_eq!(
+            Command::parse(&args("kanshi desk --dry-run")).unwrap(),
+            Command::Kanshi { profile: Some("desk".to_string()), dry_run: true }
+        );
+        execute(Command::parse(&args("kanshi desk --dry-run")).unwrap(), &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "@@ -1,3 +1,7 @@\n profile other {\n \toutput eDP-1 enable\n }\n\
+            +\n\
+            +profile desk {\n\
+            +\toutput \"Some Description 1\" enable mode 1920x1080@60Hz position 0,0 scale 1 transform normal\n\
+            +}\n"
+        );
+
+        execute(Command::Kanshi { profile: None, dry_run: false }, &mut backend, &mut runner, &config, &mut vec![]).unwrap();
+        let content = std::fs::read_to_string(&path)?;
+        assert!(content.starts_with("profile other {"));
+        assert!(content.contains("profile Some-Description-1_Some-Description-2 {\n\toutput \"Some Description 1\" enable"));
+
+        assert!(matches!(
+            execute(Command::Kanshi { profile: Some("missing".to_string()), dry_run: true }, &mut backend, &mut runner, &config, &mut vec![]),
+            Err(CliError::Validation(_))
+        ));
+        std::fs::remove_file(&path)
+    }
+
+    #[test]
+    fn apply_with_wlr_randr() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-apply-{}.conf", std::process::id()));
+        std::fs::write(&path, "monitor = desc:Some Description 1, 1920x1080@60, 1280x0, 1.5\n")?;
+        let mut config = Configuration {
+            monitors_config_path: path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let mut desk = test_monitors();
+        desk[1].enabled = true;
+        config.profiles.insert("desk".to_string(), profile::from_monitors(&desk));
+        let mut backend = FakeBackend::new(test_monitors());
+        let mut runner = FakeRunner::default();
+
+        assert_eq!(
+            Command::parse(&args("apply --dry-run")).unwrap(),
+            Command::Apply { profile: None, dry_run: true }
+        );
+        assert!(matches!(Command::parse(&args("apply desk other")), Err(CliError::Validation(message)) if message == "apply expects at most one profile"));
+
+        let mut out = vec![];
+        execute(Command::parse(&args("apply --dry-run")).unwrap(), &mut backend, &mut runner, &config, &mut out).unwrap();
+        execute(Command::parse(&args("apply desk --dry-run")).unwrap(), &mut backend, &mut runner, &config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "wlr-randr --output 'Monitor 1' --on --mode 1920x1080@60Hz --pos 1280,0 --scale 1.5 --transform normal --output 'Monitor 2' --off\n\
+            wlr-randr --output 'Monitor 1' --on --mode 1920x1080@60Hz --pos 0,0 --scale 1 --transform normal \
+            --output 'Monitor 2' --on --mode 1280x720@60Hz --pos 1920,0 --scale 1.25 --transform normal\n"
+        );
+        assert!(backend.applied.is_empty());
+        assert!(runner.calls.borrow().is_empty());
+
+        // the arguments are passed as they are, without shell quoting
+        execute(Command::parse(&args("apply")).unwrap(), &mut backend, &mut runner, &config, &mut vec![]).unwrap();
+        assert_eq!(runner.calls.borrow()[0][..3], ["wlr-randr", "--output", "Monitor 1"]);
+        assert!(backend.applied.is_empty());
+        std::fs::remove_file(&path)
+    }
+}
@@ -1,46 +1,177 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
-use serde::Deserialize;
-#[derive(Debug,Default, Clone, Deserialize)]
+use serde::{Deserialize, Serialize};
+use crate::backend::BackendKind;
+use crate::backup::DEFAULT_BACKUPS;
+use crate::error::{self, Error};
+use crate::monitor::Monitor;
+use crate::profile::MonitorSettings;
+use crate::writer::{ConfigFormat, Target};
+#[derive(Debug,Default, Clone, Serialize, Deserialize)]
 pub struct Configuration {
     pub monitors_config_path: String,
+    #[serde(default)]
+    pub backend: BackendKind,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Vec<MonitorSettings>>,
+    #[serde(default = "default_backups")]
+    pub backups: usize,
+    // written along with monitors_config_path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Target>,
+    // replaced by a kanshi target, still read from older configuration files
+    #[serde(default, skip_serializing)]
+    pub kanshi_config_path: Option<String>,
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
 }
+fn default_backups() -> usize {
+    DEFAULT_BACKUPS
+}
+
+const DEFAULT_MONITORS_CONFIG_PATH: &str = "~/.config/hypr/hyprland/monitors.conf";
+const DEFAULT_SWAY_CONFIG_PATH: &str = "~/.config/sway/outputs.conf";
+
 impl Configuration {
-    pub fn get() -> Self {
-        let config_json_path = dirs::home_dir()
-             .map(|p| p.join(".config/display-tui/config.json"))
-             .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf());
-        match !config_json_path.exists() {
+    pub fn get() -> error::Result<Self> {
+        let config_json_path = Configuration::default_path();
+        let mut config = match !config_json_path.exists() {
             true => {
-                Configuration::create_default_config(&config_json_path)
+                Configuration::create_default_config(&config_json_path)?
             },
             false => {
-                Configuration::load_config()
+                Configuration::load_config(&config_json_path)?
             }
+        };
+        config.path = Some(config_json_path);
+        Ok(config)
+    }
+    // used when the configuration file cannot be read, nothing is written back to it
+    pub fn fallback() -> Self {
+        let (backend, monitors_config_path) = match std::env::var_os("SWAYSOCK") {
+            Some(_) => (BackendKind::Sway, DEFAULT_SWAY_CONFIG_PATH),
+            None => (BackendKind::WlrRandr, DEFAULT_MONITORS_CONFIG_PATH),
+        };
+        Configuration {
+            monitors_config_path: monitors_config_path.to_string(),
+            backend,
+            backups: DEFAULT_BACKUPS,
+            ..Default::default()
         }
+    }
+    fn default_path() -> PathBuf {
+        dirs::home_dir()
+             .map(|p| p.join(".config/display-tui/config.json"))
+             .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf())
+    }
+    fn create_default_config(config_json_path: &Path) -> error::Result<Self> {
+        let config = Configuration::fallback();
+        let default_config =format!(
+            "{{\n  \"monitors_config_path\": \"{}\",\n  \"backend\": {}\n}}",
+            config.monitors_config_path,
+            serde_json::to_string(&config.backend).unwrap_or_default()
+        );
+        if let Some(dir) = config_json_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(config_json_path, default_config)?;
+        Ok(config)
+    }
+    fn load_config(config_json_path: &Path) -> error::Result<Self> {
+        let config_content = fs::read_to_string(config_json_path)?;
+        
+        let mut config: Configuration = serde_json::from_str(&config_content).map_err(|e| Error::Config {
+            path: config_json_path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        config.migrate();
+        Ok(config)
+    }
+    fn migrate(&mut self) {
+        if let Some(path) = self.kanshi_config_path.take()
+            && !self.targets.iter().any(|target| target.format == ConfigFormat::Kanshi) {
+            self.targets.push(Target { format: ConfigFormat::Kanshi, path });
+        }
+    }
+    // the monitors config is written in the syntax of the compositor driven by the backend
+    pub fn monitors_target(&self) -> Target {
+        let format = match self.backend {
+            BackendKind::Sway => ConfigFormat::Sway,
+            BackendKind::WlrRandr | BackendKind::Hyprctl => ConfigFormat::Hyprland,
+        };
+        Target { format, path: self.monitors_config_path.clone() }
+    }
+    pub fn targets(&self) -> Vec<Target> {
+        let mut targets = vec![self.monitors_target()];
+        targets.extend(self.targets.iter().cloned());
+        targets
+    }
+    pub fn merge_monitors_config(&self, monitors: &mut [Monitor]) -> error::Result<()> {
+        let target = self.monitors_target();
+        let content = target.read()?;
+        target.format.writer().merge(&content, monitors);
+        Ok(())
+    }
+    pub fn save(&self) -> std::io::Result<()> {
+        // the fallback configuration has no file, saving it would silently lose the profiles
+        let Some(config_json_path) = &self.path else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "configuration file could not be read, profiles are not saved",
+            ));
+        };
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(config_json_path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_invalid_config() {
+        let dir = std::env::temp_dir().join(format!("display-tui-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+
+        Configuration::create_default_config(&path).unwrap();
+        let config = Configuration::load_config(&path).unwrap();
+        assert_eq!(config.monitors_config_path, DEFAULT_MONITORS_CONFIG_PATH);
+        assert_eq!(config.backups, DEFAULT_BACKUPS);
 
+        fs::write(&path, "{ \"backend\": \"wlr-randr\" }").unwrap();
+        let error = Configuration::load_config(&path).unwrap_err();
+        assert!(matches!(&error, Error::Config { message, .. } if message.starts_with("missing field `monitors_config_path`")));
+        assert!(error.to_string().starts_with(&format!("Invalid file {}: missing field", path.display())));
 
+        let error = Configuration::load_config(&dir.join("missing.json")).unwrap_err();
+        assert!(matches!(error, Error::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+
+        fs::remove_dir_all(&dir).unwrap();
     }
-    fn create_default_config(config_json_path: &PathBuf) -> Self {
-        let default_monitors_config_path = "~/.config/hypr/hyprland/monitors.conf";
-        let default_config =format!("{{\n  \"monitors_config_path\": \"{}\"\n}}", default_monitors_config_path);
-        fs::create_dir_all(config_json_path.parent().unwrap()).expect("Failed to create config directory");
-        fs::write(config_json_path, default_config).expect("Failed to write default config file");
-        Configuration {
-            monitors_config_path: default_monitors_config_path.to_string(),
-        } 
+
+    #[test]
+    fn migrate_kanshi_config_path() {
+        let mut config: Configuration = serde_json::from_str(
+            r#"{"monitors_config_path": "monitors.conf", "kanshi_config_path": "~/kanshi/config"}"#
+        ).unwrap();
+        config.migrate();
+        assert_eq!(config.targets, vec![Target { format: ConfigFormat::Kanshi, path: "~/kanshi/config".to_string() }]);
+        let saved = serde_json::to_string(&config).unwrap();
+        assert!(saved.contains("\"targets\"") && !saved.contains("kanshi_config_path"));
     }
-    fn load_config() -> Self {
-        let config_json_path = dirs::home_dir()
-            .map(|p| p.join(".config/display-tui/config.json"))
-            .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf());
-        
-        let config_content = fs::read_to_string(config_json_path)
-            .expect("Failed to read config file");
-        
-        let config: Configuration = serde_json::from_str(&config_content)
-            .expect("Failed to parse config file");
-        
-        config
+
+    #[test]
+    fn render_in_backend_syntax() {
+        let monitors = crate::test_utils::tests::test_monitors();
+        let mut config = Configuration::fallback();
+        config.backend = BackendKind::Hyprctl;
+        let render = |config: &Configuration| config.monitors_target().format.writer().render("", &monitors).unwrap();
+        assert!(render(&config).starts_with("monitor = desc:Some Description 1, "));
+        config.backend = BackendKind::Sway;
+        assert!(render(&config).starts_with("output \"Some Description 1\" enable "));
     }
 }
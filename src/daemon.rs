@@ -0,0 +1,144 @@
+use std::env;
+use std::io::{self, BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use crate::backend::DisplayBackend;
+use crate::configuration::Configuration;
+use crate::profile;
+
+pub fn socket_path() -> io::Result<PathBuf> {
+    let runtime_dir = env::var("XDG_RUNTIME_DIR")
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "XDG_RUNTIME_DIR is not set"))?;
+    let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE")
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "HYPRLAND_INSTANCE_SIGNATURE is not set, is Hyprland running ?"))?;
+    Ok(PathBuf::from(runtime_dir)
+        .join("hypr")
+        .join(signature)
+        .join(".socket2.sock"))
+}
+
+pub fn run() -> io::Result<()> {
+    let config = Configuration::get()?;
+    let mut backend = config.backend.create();
+    let stream = UnixStream::connect(socket_path()?)?;
+    listen(BufReader::new(stream), backend.as_mut(), &config)
+}
+
+pub fn listen<R: BufRead>(reader: R, backend: &mut dyn DisplayBackend, config: &Configuration) -> io::Result<()> {
+    apply_best_profile(backend, config);
+    for line in reader.lines() {
+        if is_hotplug_event(&line?) {
+            apply_best_profile(backend, config);
+        }
+    }
+    Ok(())
+}
+
+pub fn is_hotplug_event(line: &str) -> bool {
+    // the v2 variants are sent alongside the plain ones, only react once per hotplug
+    matches!(
+        line.split_once(">>").map(|(event, _)| event),
+        Some("monitoradded") | Some("monitorremoved")
+    )
+}
+
+fn apply_best_profile(backend: &mut dyn DisplayBackend, config: &Configuration) -> Option<String> {
+    let mut monitors = match backend.get_monitors() {
+        Ok(monitors) => monitors,
+        Err(e) => {
+            eprintln!("Failed to read monitors: {}", e);
+            return None;
+        }
+    };
+    let Some((name, settings)) = profile::best_match(&config.profiles, &monitors) else {
+        println!("No profile matches the connected displays");
+        return None;
+    };
+    profile::load(settings, &mut monitors);
+    match backend.apply(&monitors) {
+        Ok(()) => {
+            println!("Applied profile {}", name);
+            Some(name.clone())
+        },
+        Err(e) => {
+            eprintln!("Failed to apply profile {}: {}", name, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::io::Write;
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+    use crate::backend::fake::FakeBackend;
+    use crate::monitor::{Monitor, Position};
+    use crate::test_utils::tests::test_monitors;
+
+    // the outputs reported by the compositor after each hotplug event
+    #[derive(Debug)]
+    struct Hotplug {
+        states: Vec<Vec<Monitor>>,
+        backend: FakeBackend,
+    }
+
+    impl DisplayBackend for Hotplug {
+        fn get_monitors(&mut self) -> io::Result<Vec<Monitor>> {
+            if !self.states.is_empty() {
+                self.backend.monitors = self.states.remove(0);
+            }
+            self.backend.get_monitors()
+        }
+
+        fn apply(&mut self, monitors: &[Monitor]) -> io::Result<()> {
+            self.backend.apply(monitors)
+        }
+    }
+
+    #[test]
+    fn replay_hotplug_events() -> io::Result<()> {
+        let socket = env::temp_dir().join(format!("display-tui-{}.socket2.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket)?;
+        let server = thread::spawn(move || -> io::Result<()> {
+            let (mut stream, _) = listener.accept()?;
+            stream.write_all(include_bytes!("../tests/fixtures/hyprland_events_dock.txt"))
+        });
+
+        let mut docked = test_monitors();
+        docked[0].position = Some(Position { x: 1280, y: 0 });
+        docked[1].enabled = true;
+        docked[1].position = Some(Position { x: 0, y: 0 });
+        let mut laptop = test_monitors();
+        laptop.truncate(1);
+        let config = Configuration {
+            profiles: BTreeMap::from([
+                ("docked".to_string(), profile::from_monitors(&docked)),
+                ("laptop".to_string(), profile::from_monitors(&laptop)),
+            ]),
+            ..Default::default()
+        };
+
+        // the fixture plugs the second output in and out again
+        let mut backend = Hotplug {
+            states: vec![laptop.clone(), test_monitors(), laptop.clone()],
+            backend: FakeBackend::new(vec![]),
+        };
+        let stream = UnixStream::connect(&socket)?;
+        listen(BufReader::new(stream), &mut backend, &config)?;
+        server.join().unwrap()?;
+        std::fs::remove_file(&socket)?;
+
+        let applied = &backend.backend.applied;
+        assert_eq!(applied.len(), 3);
+        assert_eq!(applied[0], laptop);
+        assert_eq!(applied[1].len(), 2);
+        assert_eq!(applied[1][0].position, Some(Position { x: 1280, y: 0 }));
+        assert!(applied[1][1].enabled);
+        assert_eq!(applied[2], laptop);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+pub const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    File(String),
+    Hunk(String),
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+impl DiffLine {
+    pub fn text(&self) -> String {
+        match self {
+            DiffLine::File(path) => format!("=== {}", path),
+            DiffLine::Hunk(header) => header.clone(),
+            DiffLine::Context(line) => format!(" {}", line),
+            DiffLine::Added(line) => format!("+{}", line),
+            DiffLine::Removed(line) => format!("-{}", line),
+        }
+    }
+}
+
+// unified line diff, empty when both contents have the same lines
+pub fn unified(old: &str, new: &str, context: usize) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // longest common subsequence of the suffixes
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(DiffLine::Context(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Context(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let mut output = vec![];
+    let mut next = 0;
+    while next < changes.len() {
+        // group the changes whose contexts touch
+        let mut last = next;
+        while last + 1 < changes.len() && changes[last + 1] - changes[last] <= 2 * context + 1 {
+            last += 1;
+        }
+        let start = changes[next].saturating_sub(context);
+        let end = (changes[last] + context + 1).min(lines.len());
+        let count = |range: &[DiffLine], removed: bool| {
+            range
+                .iter()
+                .filter(|line| match line {
+                    DiffLine::Context(_) => true,
+                    DiffLine::Removed(_) => removed,
+                    DiffLine::Added(_) => !removed,
+                    DiffLine::File(_) | DiffLine::Hunk(_) => false,
+                })
+                .count()
+        };
+        let (old_before, new_before) = (count(&lines[..start], true), count(&lines[..start], false));
+        let (old_count, new_count) = (count(&lines[start..end], true), count(&lines[start..end], false));
+        let first = |before: usize, count: usize| if count == 0 { before } else { before + 1 };
+        output.push(DiffLine::Hunk(format!(
+            "@@ -{},{} +{},{} @@",
+            first(old_before, old_count),
+            old_count,
+            first(new_before, new_count),
+            new_count
+        )));
+        output.extend_from_slice(&lines[start..end]);
+        next = last + 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff() {
+        assert_eq!(unified("a\nb\n", "a\nb", CONTEXT), vec![]);
+
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\n11\n";
+        let lines: Vec<String> = unified(old, new, 1).iter().map(DiffLine::text).collect();
+        assert_eq!(lines, vec![
+            "@@ -2,3 +2,3 @@", " 2", "-3", "+three", " 4",
+            "@@ -10,1 +10,2 @@", " 10", "+11",
+        ]);
+
+        let lines: Vec<String> = unified("", "monitor = DP-1, preferred, auto, 1", CONTEXT).iter().map(DiffLine::text).collect();
+        assert_eq!(lines, vec!["@@ -0,0 +1,1 @@", "+monitor = DP-1, preferred, auto, 1"]);
+    }
+}
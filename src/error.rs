@@ -0,0 +1,149 @@
+use std::fmt;
+use std::io;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Clear,Paragraph,Widget,Wrap},
+};
+use crate::App;
+
+#[derive(Debug)]
+pub enum Error {
+    // the display backend failed: missing tool, unexpected output or rejected layout
+    Backend(io::Error),
+    // reading or writing a file failed
+    Io(io::Error),
+    Config { path: String, message: String },
+    MissingMode(String),
+    MissingPosition(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Backend(e) => write!(f, "Display backend error: {}", e),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Config { path, message } => write!(f, "Invalid file {}: {}", path, message),
+            Error::MissingMode(name) => write!(f, "{} has no mode", name),
+            Error::MissingPosition(name) => write!(f, "{} has no position", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Backend(e) | Error::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ErrorPopup<'a> {
+    pub error: &'a Error,
+}
+
+impl<'a> ErrorPopup<'a> {
+    pub fn new(error: &'a Error) -> Self {
+        ErrorPopup { error }
+    }
+
+    // any key closes the popup
+    pub fn handle_events(app:&mut App, _key_event: KeyEvent) {
+        app.error = None;
+    }
+
+    pub fn report<T>(app:&mut App, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                app.error = Some(e);
+                None
+            }
+        }
+    }
+
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.min(60);
+        let lines = self.error.to_string().len() as u16 / width.saturating_sub(4).max(1) + 1;
+        let height = area.height.min(lines + 4);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Error ".white().bold());
+        let instructions = Line::from(vec![
+            " Close ".white(),
+            "<any key> ".blue().bold(),
+        ]);
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Red));
+
+        let text = vec![
+            Line::from(""),
+            Line::from(self.error.to_string().red()).centered(),
+        ];
+
+        Clear.render(area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_error_popup() {
+        let error = Error::MissingMode("DP-1".to_string());
+        let popup = ErrorPopup::new(&error);
+        assert_eq!(popup.popup_area(Rect::new(0, 0, 80, 20)), Rect::new(10, 7, 60, 5));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 5));
+        popup.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━ Error ━━━━━━━━━━━┓",
+            "┃                            ┃",
+            "┃      DP-1 has no mode      ┃",
+            "┃                            ┃",
+            "┗━━━━━ Close <any key> ━━━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Red);
+        expected.set_style(Rect::new(0, 0, 30, 1), border_style);
+        expected.set_style(Rect::new(11, 0, 7, 1), Style::new().fg(Color::White).bold());
+        expected.set_style(Rect::new(0, 1, 1, 3), border_style);
+        expected.set_style(Rect::new(29, 1, 1, 3), border_style);
+        expected.set_style(Rect::new(7, 2, 16, 1), Style::new().fg(Color::Red));
+        expected.set_style(Rect::new(0, 4, 30, 1), border_style);
+        expected.set_style(Rect::new(6, 4, 7, 1), Style::new().fg(Color::White));
+        expected.set_style(Rect::new(13, 4, 10, 1), Style::new().blue().bold());
+
+        assert_eq!(buf, expected);
+    }
+}
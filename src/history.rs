@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::configuration::Configuration;
+use crate::error::{self, Error};
+use crate::monitor::Monitor;
+use crate::profile::MonitorSettings;
+
+// last known settings of each output, restored when the output is enabled again
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default)]
+    pub monitors: BTreeMap<String, MonitorSettings>,
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl History {
+    pub fn load(config: &Configuration) -> error::Result<Self> {
+        let Some(path) = config.path.as_ref().and_then(|path| path.parent()).map(|dir| dir.join("history.json")) else {
+            return Ok(History::default());
+        };
+        let mut history: History = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| Error::Config {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => History::default(),
+            Err(e) => return Err(e.into()),
+        };
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    pub fn remember(&mut self, monitors: &[Monitor]) {
+        for monitor in monitors.iter().filter(|monitor| monitor.enabled) {
+            self.monitors.insert(monitor.key(), MonitorSettings::from_monitor(monitor, monitors));
+        }
+    }
+
+    pub fn restore(&self, monitor: &mut Monitor) -> bool {
+        let Some(settings) = self.monitors.get(&monitor.key()) else {
+            return false;
+        };
+        let mut settings = settings.clone();
+        settings.enabled = true;
+        settings.mirror_of = None;
+        settings.apply_to(monitor, &[]);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::Position;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn remember_and_restore() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("display-tui-history-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let config = Configuration {
+            path: Some(dir.join("config.json")),
+            ..Default::default()
+        };
+        let mut monitors = test_monitors();
+        monitors[0].position = Some(Position { x: -1920, y: 200 });
+        monitors[0].scale = Some(1.5);
+        monitors[0].set_current_resolution(1);
+
+        let mut history = History::load(&config)?;
+        history.remember(&monitors);
+        assert_eq!(history.monitors.len(), 1);
+        history.save()?;
+
+        let history = History::load(&config)?;
+        let mut monitor = test_monitors().remove(0);
+        monitor.enabled = false;
+        assert!(history.restore(&mut monitor));
+        assert!(monitor.enabled);
+        assert_eq!(monitor.position, Some(Position { x: -1920, y: 200 }));
+        assert_eq!(monitor.scale, Some(1.5));
+        assert!(monitor.modes[1].current);
+
+        assert!(!history.restore(&mut test_monitors().remove(1)));
+
+        fs::write(dir.join("history.json"), "{ \"monitors\": [] }")?;
+        let error = History::load(&config).unwrap_err();
+        assert!(matches!(&error, Error::Config { path, .. } if path.ends_with("history.json")));
+        fs::remove_dir_all(&dir)
+    }
+}
@@ -0,0 +1,266 @@
+use crate::monitor::{Monitor, Position};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleTarget {
+    Name(String),
+    Desc(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorRule {
+    pub target: RuleTarget,
+    pub disabled: bool,
+    pub mode: Option<String>,
+    pub position: Option<String>,
+    pub scale: Option<String>,
+    pub options: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigLine {
+    Monitor(MonitorRule),
+    Other(String),
+}
+
+pub fn parse(content: &str) -> Vec<ConfigLine> {
+    content.lines().map(parse_line).collect()
+}
+
+pub fn parse_line(line: &str) -> ConfigLine {
+    match parse_rule(line) {
+        Some(rule) => ConfigLine::Monitor(rule),
+        None => ConfigLine::Other(line.to_string()),
+    }
+}
+
+fn parse_rule(line: &str) -> Option<MonitorRule> {
+    let line = line.split('#').next()?.trim();
+    let (keyword, value) = line.split_once('=')?;
+    if keyword.trim() != "monitor" {
+        return None;
+    }
+    let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+    let target = match fields[0].strip_prefix("desc:") {
+        Some(desc) => RuleTarget::Desc(desc.trim().to_string()),
+        None => RuleTarget::Name(fields[0].to_string()),
+    };
+    if fields[0].is_empty() {
+        return None;
+    }
+    let mut rule = MonitorRule {
+        target,
+        disabled: fields.get(1) == Some(&"disabled"),
+        mode: None,
+        position: None,
+        scale: None,
+        options: vec![],
+    };
+    if rule.disabled {
+        return Some(rule);
+    }
+    rule.mode = fields.get(1).map(|s| s.to_string());
+    rule.position = fields.get(2).map(|s| s.to_string());
+    rule.scale = fields.get(3).map(|s| s.to_string());
+    // an unpaired trailing token is kept as a key without value
+    let mut options = fields.iter().skip(4).filter(|field| !field.is_empty());
+    while let Some(key) = options.next() {
+        let value = options.next().unwrap_or(&"");
+        rule.options.push((key.to_string(), value.to_string()));
+    }
+    Some(rule)
+}
+
+impl MonitorRule {
+    pub fn matches(&self, monitor: &Monitor) -> bool {
+        match &self.target {
+            RuleTarget::Name(name) => &monitor.name == name,
+            RuleTarget::Desc(desc) => {
+                monitor.identity() == *desc
+                    || monitor.description.as_deref().is_some_and(|description| description.starts_with(desc.as_str()))
+            },
+        }
+    }
+
+    pub fn apply_to(&self, monitor: &mut Monitor) {
+        monitor.enabled = !self.disabled;
+        if self.disabled {
+            return;
+        }
+        if let Some(index) = self.mode.as_deref().and_then(|mode| find_mode(monitor, mode)) {
+            monitor.set_current_resolution(index);
+        }
+        if let Some((x, y)) = self.position.as_deref().and_then(parse_position) {
+            monitor.position = Some(Position { x, y });
+        }
+        if let Some(scale) = self.scale.as_deref().and_then(|scale| scale.parse::<f32>().ok()) {
+            monitor.scale = Some(scale);
+        }
+        monitor.extra_options.clear();
+        for (key, value) in &self.options {
+            match (key.as_str(), value.as_str()) {
+                ("transform", value) if value.parse::<u8>().is_ok_and(|t| t < 8) => {
+                    monitor.transform = value.parse().unwrap_or(0);
+                },
+                ("mirror", value) => monitor.mirror_of = Some(value.to_string()),
+                ("vrr", "0") => monitor.vrr = false,
+                ("vrr", "1") => monitor.vrr = true,
+                _ => monitor.extra_options.push((key.clone(), value.clone())),
+            }
+        }
+    }
+}
+
+pub fn find_mode(monitor: &Monitor, mode: &str) -> Option<usize> {
+    let (size, refresh) = match mode.trim_end_matches("Hz").split_once('@') {
+        Some((size, refresh)) => (size, Some(refresh.parse::<f32>().ok()?)),
+        None => (mode, None),
+    };
+    let (width, height) = size.split_once('x')?;
+    let (width, height): (i32, i32) = (width.parse().ok()?, height.parse().ok()?);
+    monitor.modes
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.width == width && m.height == height)
+        .filter(|(_, m)| refresh.is_none_or(|refresh| (m.refresh - refresh).abs() < 0.5))
+        .max_by(|(_, a), (_, b)| a.refresh.total_cmp(&b.refresh))
+        .map(|(index, _)| index)
+}
+
+fn parse_position(position: &str) -> Option<(i32, i32)> {
+    let (x, y) = position.split_once('x')?;
+    Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+pub fn merge(content: &str, monitors: &mut [Monitor]) {
+    for line in parse(content) {
+        if let ConfigLine::Monitor(rule) = line
+            && let Some(monitor) = monitors.iter_mut().find(|monitor| rule.matches(monitor)) {
+            rule.apply_to(monitor);
+        }
+    }
+}
+
+// the trailing comment of a line, with the spaces before it
+fn comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(index) => &line[line[..index].trim_end().len()..],
+        None => "",
+    }
+}
+
+pub fn render(content: &str, monitors: &[Monitor]) -> crate::error::Result<String> {
+    let mut written = vec![false; monitors.len()];
+    let mut output = String::new();
+    for line in content.lines() {
+        match parse_line(line) {
+            ConfigLine::Monitor(rule) => match monitors.iter().position(|monitor| rule.matches(monitor)) {
+                Some(index) if !written[index] => {
+                    written[index] = true;
+                    output.push_str(&monitors[index].to_hyprland_config()?);
+                    output.push_str(comment(line));
+                    output.push('\n');
+                },
+                // a second rule for an output we already wrote would override it, it is kept commented out
+                Some(_) => {
+                    output.push_str(&format!("# {}\n", line));
+                },
+                None => {
+                    output.push_str(line);
+                    output.push('\n');
+                },
+            },
+            ConfigLine::Other(line) => {
+                output.push_str(&line);
+                output.push('\n');
+            },
+        }
+    }
+    for (monitor, written) in monitors.iter().zip(written) {
+        if !written {
+            output.push_str(&monitor.to_hyprland_config()?);
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    const CONFIG: &str = "# generated by hand
+monitor = desc:Some Description 1, 1280x720@60, 100x200, 1.5, transform, 1, bitdepth, 10, vrr, 1, cm
+monitor=Monitor 2,disabled  # docked only
+monitor = HDMI-A-1, preferred, auto, 1, mirror, Monitor 1
+
+$primary = Monitor 1
+";
+
+    #[test]
+    fn parse_monitor_rules() {
+        let lines = parse(CONFIG);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], ConfigLine::Other("# generated by hand".to_string()));
+        let ConfigLine::Monitor(rule) = &lines[1] else {
+            panic!("expected a monitor rule");
+        };
+        assert_eq!(rule.target, RuleTarget::Desc("Some Description 1".to_string()));
+        assert_eq!(rule.mode.as_deref(), Some("1280x720@60"));
+        assert_eq!(rule.options[1], ("bitdepth".to_string(), "10".to_string()));
+        assert_eq!(rule.options[3], ("cm".to_string(), String::new()));
+        assert_eq!(
+            lines[2],
+            ConfigLine::Monitor(MonitorRule {
+                target: RuleTarget::Name("Monitor 2".to_string()),
+                disabled: true,
+                mode: None,
+                position: None,
+                scale: None,
+                options: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn merge_and_render_monitor_rules() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        merge(CONFIG, &mut monitors);
+
+        let monitor = &monitors[0];
+        assert!(monitor.modes[1].current);
+        assert_eq!(monitor.position, Some(Position { x: 100, y: 200 }));
+        assert_eq!(monitor.scale, Some(1.5));
+        assert_eq!(monitor.transform, 1);
+        assert!(monitor.vrr);
+        assert!(!monitors[1].enabled);
+
+        assert_eq!(
+            render(CONFIG, &monitors).unwrap(),
+            "# generated by hand
+monitor = desc:Some Description 1, 1280x720@60, 100x200, 1.5, transform, 1, vrr, 1, bitdepth, 10, cm
+monitor = Monitor 2, disabled  # docked only
+monitor = HDMI-A-1, preferred, auto, 1, mirror, Monitor 1
+
+$primary = Monitor 1
+"
+        );
+    }
+
+    #[test]
+    fn comment_out_duplicate_rules() {
+        let content = "monitor = Monitor 1, 1920x1080@60, 0x0, 1\nmonitor = Monitor 1, 1280x720@60, 0x0, 1\n";
+        let mut monitors = test_monitors();
+        merge(content, &mut monitors);
+        let rendered = render(content, &monitors).unwrap();
+        assert_eq!(
+            rendered,
+            "monitor = desc:Some Description 1, 1280x720@60, 0x0, 1
+# monitor = Monitor 1, 1280x720@60, 0x0, 1
+monitor = Monitor 2, disabled
+"
+        );
+        assert_eq!(render(&rendered, &monitors).unwrap(), rendered);
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use crate::backup;
+use crate::error;
+use crate::monitor::{transform_name, Monitor};
+use crate::profile::MonitorSettings;
+
+pub const DEFAULT_CONFIG_PATH: &str = "~/.config/kanshi/config";
+
+// kanshi profile names are single words
+pub fn profile_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<&str>>().join("-")
+}
+
+// the profile of the current layout is named after the displays like its outputs, one profile per set of displays whatever their ports
+pub fn layout_name(monitors: &[Monitor]) -> String {
+    let mut names: Vec<String> = monitors.iter().map(Monitor::key).collect();
+    names.sort();
+    profile_name(&names.join("_"))
+}
+
+pub fn output_line(settings: &MonitorSettings) -> String {
+    let mut line = format!("output \"{}\"", settings.output);
+    if !settings.enabled {
+        line.push_str(" disable");
+        return line;
+    }
+    line.push_str(" enable");
+    if let Some(mode) = &settings.mode {
+        line.push_str(&format!(" mode {}x{}@{}Hz", mode.width, mode.height, mode.refresh));
+    }
+    if let Some(position) = &settings.position {
+        line.push_str(&format!(" position {},{}", position.x, position.y));
+    }
+    if let Some(scale) = settings.scale {
+        line.push_str(&format!(" scale {}", scale));
+    }
+    line.push_str(&format!(" transform {}", transform_name(settings.transform)));
+    line
+}
+
+pub fn profile_block(name: &str, profile: &[MonitorSettings]) -> String {
+    let mut block = format!("profile {} {{\n", profile_name(name));
+    for settings in profile {
+        block.push('\t');
+        block.push_str(&output_line(settings));
+        block.push('\n');
+    }
+    block.push_str("}\n");
+    block
+}
+
+fn starts_profile(line: &str, name: &str) -> bool {
+    let mut words = line.split('#').next().unwrap_or_default().split_whitespace();
+    words.next() == Some("profile") && words.next() == Some(name) && line.contains('{')
+}
+
+// replaces the profile with the same name, the other profiles and directives are kept
+pub fn merge(content: &str, name: &str, block: &str) -> String {
+    let name = profile_name(name);
+    let mut output = String::new();
+    let mut replaced = false;
+    let mut depth = 0;
+    for line in content.lines() {
+        if depth > 0 {
+            depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+            continue;
+        }
+        if !replaced && starts_profile(line, &name) {
+            replaced = true;
+            depth = line.matches('{').count() as i32 - line.matches('}').count() as i32;
+            output.push_str(block);
+            continue;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    if !replaced {
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(block);
+    }
+    output
+}
+
+pub fn preview(path: &str, name: &str, profile: &[MonitorSettings]) -> error::Result<(String, String)> {
+    let expanded_path = shellexpand::tilde(path).to_string();
+    let existing = match std::fs::read_to_string(&expanded_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let rendered = merge(&existing, name, &profile_block(name, profile));
+    Ok((existing, rendered))
+}
+
+pub fn save(path: &str, name: &str, profile: &[MonitorSettings], backups: usize) -> error::Result<()> {
+    let (_, rendered) = preview(path, name, profile)?;
+    let expanded_path = PathBuf::from(shellexpand::tilde(path).to_string());
+    if let Some(dir) = expanded_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    backup::create(&expanded_path, &rendered, backups)?;
+    backup::write_atomic(&expanded_path, &rendered)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile;
+    use crate::test_utils::tests::test_monitors;
+
+    const CONFIG: &str = "# kanshi
+profile docked {
+\toutput eDP-1 disable
+\toutput \"Dell Inc. DELL U2720Q 8LXMZ13\" {
+\t\tmode 2560x1440
+\t}
+\texec notify-send docked
+}
+
+profile Some-Description-1_Some-Description-2 {
+\toutput \"Some Description 1\" enable
+}
+";
+
+    #[test]
+    fn export_and_merge_profiles() {
+        let mut monitors = test_monitors();
+        monitors[0].transform = 1;
+        let name = layout_name(&monitors);
+        assert_eq!(name, "Some-Description-1_Some-Description-2");
+        // the same displays on other ports replace the same profile
+        let mut moved = monitors.clone();
+        moved[0].name = "DP-3".to_string();
+        assert_eq!(layout_name(&moved), name);
+        let block = profile_block(&name, &profile::from_monitors(&monitors));
+        assert_eq!(
+            block,
+            "profile Some-Description-1_Some-Description-2 {
+\toutput \"Some Description 1\" enable mode 1920x1080@60Hz position 0,0 scale 1 transform 90
+\toutput \"Some Description 2\" disable
+}
+"
+        );
+
+        let merged = merge(CONFIG, &name, &block);
+        assert_eq!(merged, format!("{}{}", &CONFIG[..CONFIG.find("profile Some").unwrap()], block));
+        assert_eq!(merge(&merged, &name, &block), merged);
+
+        let added = merge(CONFIG, "undocked laptop", &profile_block("undocked laptop", &[]));
+        assert_eq!(added, format!("{}\nprofile undocked-laptop {{\n}}\n", CONFIG));
+        assert_eq!(merge("", "empty", "profile empty {\n}\n"), "profile empty {\n}\n");
+    }
+}
@@ -0,0 +1,373 @@
+use crate::monitor::{Monitor, Position};
+
+pub const GAP_THRESHOLD: f64 = 100.0;
+pub const EPSILON: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogicalRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LogicalRect {
+    pub fn of(monitor: &Monitor) -> Option<Self> {
+        let (width, height) = monitor.logical_size()?;
+        let position = monitor.position.as_ref()?;
+        Some(LogicalRect {
+            x: position.x as f64,
+            y: position.y as f64,
+            width,
+            height,
+        })
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    fn overlap(a_start: f64, a_end: f64, b_start: f64, b_end: f64) -> f64 {
+        a_end.min(b_end) - a_start.max(b_start)
+    }
+
+    pub fn horizontal_overlap(&self, other: &LogicalRect) -> f64 {
+        LogicalRect::overlap(self.x, self.right(), other.x, other.right())
+    }
+
+    pub fn vertical_overlap(&self, other: &LogicalRect) -> f64 {
+        LogicalRect::overlap(self.y, self.bottom(), other.y, other.bottom())
+    }
+
+    pub fn overlaps(&self, other: &LogicalRect) -> bool {
+        self.horizontal_overlap(other) > EPSILON && self.vertical_overlap(other) > EPSILON
+    }
+
+    pub fn touches(&self, other: &LogicalRect) -> bool {
+        let horizontal = self.horizontal_overlap(other);
+        let vertical = self.vertical_overlap(other);
+        (horizontal.abs() <= EPSILON && vertical > EPSILON) || (vertical.abs() <= EPSILON && horizontal > EPSILON)
+    }
+
+    pub fn gap(&self, other: &LogicalRect) -> Option<f64> {
+        let horizontal = self.horizontal_overlap(other);
+        let vertical = self.vertical_overlap(other);
+        let gap = if vertical > EPSILON && horizontal < -EPSILON {
+            -horizontal
+        } else if horizontal > EPSILON && vertical < -EPSILON {
+            -vertical
+        } else {
+            return None;
+        };
+        (gap <= GAP_THRESHOLD).then_some(gap)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    Overlap(usize, usize),
+    Gap(usize, usize, f64),
+    Island(Vec<usize>),
+    Mirror(usize, String),
+}
+
+impl LayoutIssue {
+    pub fn monitors(&self) -> Vec<usize> {
+        match self {
+            LayoutIssue::Overlap(a, b) | LayoutIssue::Gap(a, b, _) => vec![*a, *b],
+            LayoutIssue::Island(island) => island.clone(),
+            LayoutIssue::Mirror(index, _) => vec![*index],
+        }
+    }
+
+    pub fn message(&self, monitors: &[Monitor]) -> String {
+        match self {
+            LayoutIssue::Overlap(a, b) => format!("{} overlaps {}", monitors[*a].name, monitors[*b].name),
+            LayoutIssue::Gap(a, b, gap) => format!("{}px gap between {} and {}", gap.round(), monitors[*a].name, monitors[*b].name),
+            LayoutIssue::Island(island) => format!(
+                "{} not connected to the layout",
+                island.iter().map(|index| monitors[*index].name.as_str()).collect::<Vec<&str>>().join(", ")
+            ),
+            LayoutIssue::Mirror(index, reason) => format!("{}: {}", monitors[*index].name, reason),
+        }
+    }
+}
+
+fn placed(monitors: &[Monitor]) -> Vec<(usize, LogicalRect)> {
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(_, monitor)| monitor.enabled && !monitor.disconnected && monitor.mirror_of.is_none())
+        .filter_map(|(index, monitor)| LogicalRect::of(monitor).map(|rect| (index, rect)))
+        .collect()
+}
+
+// mirrors set by a profile, the history or undo are checked like the ones of the picker
+fn mirror_issues(monitors: &[Monitor]) -> Vec<LayoutIssue> {
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(_, monitor)| monitor.enabled && !monitor.disconnected)
+        .filter_map(|(index, monitor)| {
+            let source = monitor.mirror_of.as_deref()?;
+            let connected = monitors.iter().any(|other| other.name == source && !other.disconnected);
+            let reason = if connected {
+                Monitor::validate_mirror(monitors, index, source).err()?
+            } else {
+                format!("Unknown output {}", source)
+            };
+            Some(LayoutIssue::Mirror(index, reason))
+        })
+        .collect()
+}
+
+pub fn validate(monitors: &[Monitor]) -> Vec<LayoutIssue> {
+    let placed = placed(monitors);
+    let mut issues = mirror_issues(monitors);
+    for (i, (a, a_rect)) in placed.iter().enumerate() {
+        for (b, b_rect) in placed.iter().skip(i + 1) {
+            if a_rect.overlaps(b_rect) {
+                issues.push(LayoutIssue::Overlap(*a, *b));
+            } else if let Some(gap) = a_rect.gap(b_rect) {
+                issues.push(LayoutIssue::Gap(*a, *b, gap));
+            }
+        }
+    }
+
+    // group the outputs reachable through shared edges (or overlaps and small gaps, already reported)
+    let mut group: Vec<usize> = (0..placed.len()).collect();
+    fn root(group: &mut Vec<usize>, i: usize) -> usize {
+        if group[i] != i {
+            group[i] = root(group, group[i]);
+        }
+        group[i]
+    }
+    for i in 0..placed.len() {
+        for j in i + 1..placed.len() {
+            let (a, b) = (&placed[i].1, &placed[j].1);
+            if a.touches(b) || a.overlaps(b) || a.gap(b).is_some() {
+                let (ri, rj) = (root(&mut group, i), root(&mut group, j));
+                group[ri] = rj;
+            }
+        }
+    }
+    let mut islands: Vec<Vec<usize>> = vec![];
+    let mut roots: Vec<usize> = vec![];
+    for (i, (index, _)) in placed.iter().enumerate() {
+        let r = root(&mut group, i);
+        match roots.iter().position(|known| *known == r) {
+            Some(position) => islands[position].push(*index),
+            None => {
+                roots.push(r);
+                islands.push(vec![*index]);
+            }
+        }
+    }
+    if islands.len() > 1 {
+        // the biggest group is the layout, the others are islands
+        let main = islands
+            .iter()
+            .enumerate()
+            .max_by_key(|(position, island)| (island.len(), std::cmp::Reverse(*position)))
+            .map(|(position, _)| position)
+            .unwrap_or(0);
+        for (position, island) in islands.into_iter().enumerate() {
+            if position != main {
+                issues.push(LayoutIssue::Island(island));
+            }
+        }
+    }
+    issues
+}
+
+pub fn invalid_monitors(monitors: &[Monitor]) -> Vec<usize> {
+    let mut invalid: Vec<usize> = validate(monitors)
+        .iter()
+        .flat_map(LayoutIssue::monitors)
+        .collect();
+    invalid.sort();
+    invalid.dedup();
+    invalid
+}
+
+// flush to the right of the bounding box of the placed outputs, top aligned
+pub fn next_free_position(monitors: &[Monitor]) -> Position {
+    let placed = placed(monitors);
+    if placed.is_empty() {
+        return Position { x: 0, y: 0 };
+    }
+    let right = placed.iter().map(|(_, rect)| rect.right()).fold(f64::MIN, f64::max);
+    let top = placed.iter().map(|(_, rect)| rect.y).fold(f64::MAX, f64::min);
+    Position { x: right.round() as i32, y: top.round() as i32 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arrangement {
+    Row,
+    Column,
+    Grid,
+}
+
+impl Arrangement {
+    pub const ALL: [Arrangement; 3] = [Arrangement::Row, Arrangement::Column, Arrangement::Grid];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Arrangement::Row => "Row",
+            Arrangement::Column => "Column",
+            Arrangement::Grid => "Grid",
+        }
+    }
+}
+
+// placed outputs from left to right, then top to bottom
+pub fn position_order(monitors: &[Monitor]) -> Vec<usize> {
+    let mut placed = placed(monitors);
+    placed.sort_by(|(_, a), (_, b)| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    placed.into_iter().map(|(index, _)| index).collect()
+}
+
+// placed outputs in the order of the list
+pub fn list_order(monitors: &[Monitor], order: &[usize]) -> Vec<usize> {
+    let placed: Vec<usize> = placed(monitors).into_iter().map(|(index, _)| index).collect();
+    order.iter().copied().filter(|index| placed.contains(index)).collect()
+}
+
+pub fn arrange(monitors: &mut [Monitor], arrangement: Arrangement, order: &[usize]) {
+    let sizes: Vec<(usize, i32, i32)> = order
+        .iter()
+        .filter_map(|index| {
+            let (width, height) = monitors[*index].logical_size()?;
+            Some((*index, width.round() as i32, height.round() as i32))
+        })
+        .collect();
+    let columns = match arrangement {
+        Arrangement::Row => sizes.len().max(1),
+        Arrangement::Column => 1,
+        Arrangement::Grid => (sizes.len() as f64).sqrt().ceil().max(1.0) as usize,
+    };
+    // rows are packed from the left, each output resting on the outputs of the previous rows it is under
+    // so a shorter output in a row does not leave a gap above the next row
+    let mut above: Vec<(i32, i32, i32)> = vec![];
+    for row in sizes.chunks(columns) {
+        let mut x = 0;
+        let mut placed = vec![];
+        for (index, width, height) in row {
+            let y = above
+                .iter()
+                .filter(|(left, right, _)| *left < x + width && *right > x)
+                .map(|(_, _, bottom)| *bottom)
+                .max()
+                .unwrap_or(0);
+            monitors[*index].position = Some(Position { x, y });
+            placed.push((x, x + width, y + height));
+            x += width;
+        }
+        above.extend(placed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::Position;
+    use crate::test_utils::tests::test_monitors;
+
+    fn layout(second: Position) -> Vec<Monitor> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.0);
+        monitors[1].position = Some(second);
+        monitors
+    }
+
+    #[test]
+    fn validate_layouts() {
+        assert_eq!(validate(&layout(Position { x: 1920, y: 0 })), vec![]);
+        assert_eq!(validate(&layout(Position { x: 0, y: 1080 })), vec![]);
+        assert_eq!(validate(&layout(Position { x: 1900, y: 0 })), vec![LayoutIssue::Overlap(0, 1)]);
+        assert_eq!(
+            validate(&layout(Position { x: 1930, y: 0 })),
+            vec![LayoutIssue::Gap(0, 1, 10.0)]
+        );
+        assert_eq!(validate(&layout(Position { x: 5000, y: 0 })), vec![LayoutIssue::Island(vec![1])]);
+        // corners only touching do not connect the outputs
+        assert_eq!(validate(&layout(Position { x: 1920, y: 1080 })), vec![LayoutIssue::Island(vec![1])]);
+
+        let monitors = layout(Position { x: 1930, y: 0 });
+        assert_eq!(validate(&monitors)[0].message(&monitors), "10px gap between Monitor 1 and Monitor 2");
+        assert_eq!(invalid_monitors(&monitors), vec![0, 1]);
+    }
+
+    #[test]
+    fn validate_mirrors() {
+        let mut monitors = layout(Position { x: 1920, y: 0 });
+        monitors[1].mirror_of = Some("Monitor 1".to_string());
+        assert_eq!(validate(&monitors), vec![]);
+
+        monitors[0].enabled = false;
+        assert_eq!(
+            validate(&monitors)[0].message(&monitors),
+            "Monitor 2: Cannot mirror disabled output Monitor 1"
+        );
+
+        monitors[0].enabled = true;
+        monitors[0].mirror_of = Some("Monitor 2".to_string());
+        assert_eq!(invalid_monitors(&monitors), vec![0, 1]);
+
+        monitors[0].mirror_of = None;
+        monitors[0].disconnected = true;
+        assert_eq!(validate(&monitors), vec![LayoutIssue::Mirror(1, "Unknown output Monitor 1".to_string())]);
+    }
+
+    #[test]
+    fn arrange_layouts() {
+        let mut monitors = layout(Position { x: -3000, y: 500 });
+        let order = position_order(&monitors);
+        assert_eq!(order, vec![1, 0]);
+
+        arrange(&mut monitors, Arrangement::Row, &order);
+        assert_eq!(monitors[1].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(monitors[0].position, Some(Position { x: 1280, y: 0 }));
+        assert_eq!(validate(&monitors), vec![]);
+
+        let order = list_order(&monitors, &[0, 1, 2]);
+        arrange(&mut monitors, Arrangement::Column, &order);
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(monitors[1].position, Some(Position { x: 0, y: 1080 }));
+        assert_eq!(validate(&monitors), vec![]);
+
+        let mut third = monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.scale = Some(2.0);
+        monitors.push(third);
+        let order = list_order(&monitors, &[0, 1, 2]);
+        arrange(&mut monitors, Arrangement::Grid, &order);
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(monitors[1].position, Some(Position { x: 1920, y: 0 }));
+        assert_eq!(monitors[2].position, Some(Position { x: 0, y: 1080 }));
+        assert_eq!(validate(&monitors), vec![]);
+
+        // an output under a shorter one rests on it instead of the tallest output of the row
+        let mut fourth = monitors[1].clone();
+        fourth.name = "Monitor 4".to_string();
+        monitors.push(fourth);
+        monitors[2].scale = Some(1.0);
+        arrange(&mut monitors, Arrangement::Grid, &[0, 1, 2, 3]);
+        assert_eq!(monitors[2].position, Some(Position { x: 0, y: 1080 }));
+        assert_eq!(monitors[3].position, Some(Position { x: 1920, y: 720 }));
+        assert_eq!(validate(&monitors), vec![]);
+        monitors.pop();
+
+        // disabled outputs keep their position
+        monitors[2].enabled = false;
+        monitors[2].position = Some(Position { x: 100, y: 100 });
+        let order = list_order(&monitors, &[0, 1, 2]);
+        arrange(&mut monitors, Arrangement::Row, &order);
+        assert_eq!(monitors[2].position, Some(Position { x: 100, y: 100 }));
+    }
+}
@@ -7,7 +7,9 @@ use ratatui::{
     text::Line,
     widgets::{Cell,Block,StatefulWidget,Row,Table,TableState},
 };
-use crate::monitor::{Monitor,Position};
+use crate::restore::RestorePicker;
+use crate::layout;
+use crate::monitor::Monitor;
 
 use ratatui::layout::Constraint;
 use crate::utils::TUIMode;
@@ -17,22 +19,39 @@ use crate::App;
 pub struct MonitorList<'a> {
     pub mode: TUIMode,
     pub selected_row: Option<usize>,
+    pub modified: bool,
     pub state: TableState,
     pub monitors:&'a Vec<Monitor>,
+    pub order: Vec<usize>,
 }
 
 
 impl<'a> MonitorList<'a> {
-    pub fn new(monitors: &'a Vec<Monitor>,mode:TUIMode,selected_row:Option<usize>) -> Self {
+    pub fn new(monitors: &'a Vec<Monitor>,order: &[usize],mode:TUIMode,selected:Option<usize>,modified:bool) -> Self {
+        let order = MonitorList::order(order, monitors.len());
+        let selected_row = selected.and_then(|selected| order.iter().position(|index| *index == selected));
         MonitorList{
             mode,
             selected_row,
+            modified,
             state: TableState::default()
                 .with_selected(selected_row),
             monitors,
+            order,
         }
     }
 
+    // the indices of the monitors in list order, outputs detected later are appended
+    pub fn order(order: &[usize], len: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = vec![];
+        for index in order.iter().copied().chain(0..len) {
+            if index < len && !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+        indices
+    }
+
     pub fn handle_events(app:&mut App, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('k')=> MonitorList::previous_monitor(app),
@@ -42,49 +61,94 @@ impl<'a> MonitorList<'a> {
             KeyCode::Char('m') => MonitorList::change_mode(app,TUIMode::Move),
             KeyCode::Char('r') => MonitorList::change_mode(app,TUIMode::Resolution),
             KeyCode::Char('s') => MonitorList::change_mode(app,TUIMode::Scale),
+            KeyCode::Char('t') => MonitorList::change_mode(app,TUIMode::Transform),
+            KeyCode::Char('i') => MonitorList::change_mode(app,TUIMode::Mirror),
+            KeyCode::Char('p') => MonitorList::change_mode(app,TUIMode::Profiles),
+            KeyCode::Char('o') => MonitorList::change_mode(app,TUIMode::Arrange),
+            KeyCode::Char('b') => RestorePicker::open(app),
+            KeyCode::Char('K')=> MonitorList::reorder_monitor(app,-1),
+            KeyCode::Char('J')=> MonitorList::reorder_monitor(app,1),
             _ => {}
         }
     }
     fn change_mode(app:&mut App,mode: TUIMode) {
         app.mode = mode;
+        app.status.info(format!("{} mode", mode.label()));
     }
 
-    fn next_monitor(app:&mut App) {
-        app.selected_monitor = if app.selected_monitor >= app.monitors.len() - 1 {
-            0
-        } else {
-            app.selected_monitor + 1
+    pub fn selected_row(app:&App) -> (Vec<usize>, usize) {
+        let order = MonitorList::order(&app.list_order, app.monitors.len());
+        let row = order.iter().position(|index| *index == app.selected_monitor).unwrap_or(0);
+        (order, row)
+    }
+
+    // disconnected outputs are hidden from the map and cannot be edited, they are skipped
+    fn step_monitor(app:&mut App, forward: bool) {
+        let (order, row) = MonitorList::selected_row(app);
+        let len = order.len();
+        let next = (1..=len)
+            .map(|step| if forward { (row + step) % len } else { (row + len - step) % len })
+            .map(|row| order[row])
+            .find(|index| !app.monitors[*index].disconnected);
+        if let Some(index) = next {
+            app.selected_monitor = index;
         }
     }
 
+    fn next_monitor(app:&mut App) {
+        MonitorList::step_monitor(app, true);
+    }
+
     fn previous_monitor(app:&mut App) {
-        app.selected_monitor = if app.selected_monitor == 0 {
-            app.monitors.len() - 1
-        } else {
-            app.selected_monitor - 1
-        }
+        MonitorList::step_monitor(app, false);
     }
     
+    // the list order is the order used to auto arrange the monitors, the config keeps its own
+    fn reorder_monitor(app:&mut App, direction: isize) {
+        let (mut order, row) = MonitorList::selected_row(app);
+        let Some(target) = row.checked_add_signed(direction) else {
+            return;
+        };
+        if target < order.len() {
+            order.swap(row, target);
+            app.list_order = order;
+        }
+    }
+
     fn disable_monitor(app:&mut App) {
+        let monitor = &app.monitors[app.selected_monitor];
+        if !monitor.enabled {
+            return;
+        }
+        app.history.remember(std::slice::from_ref(monitor));
+        if let Err(e) = app.history.save() {
+            app.status.error(format!("Failed to save history: {}", e));
+        }
         app.monitors[app.selected_monitor].enabled = false;
     }
 
     fn enable_monitor(app:&mut App) {
+        if app.monitors[app.selected_monitor].enabled {
+            return;
+        }
+        if !app.history.restore(&mut app.monitors[app.selected_monitor]) {
+            let position = layout::next_free_position(&app.monitors);
+            let monitor = &mut app.monitors[app.selected_monitor];
+            monitor.position = Some(position);
+            monitor.scale = monitor.scale.or(Some(1.0));
+        }
         app.monitors[app.selected_monitor].enabled = true;
-        app.monitors[app.selected_monitor].position = Some(
-            Position {
-                x: 0,
-                y: 0,
-            }
-        );
-        app.monitors[app.selected_monitor].scale = Some(1.0);
     }
 
     fn monitors_to_rows(&self) -> Vec<Row<'static>> {
-        self.monitors
+        self.order
             .iter()
-            .map(|monitor| {
-                let name = monitor.name.clone();
+            .map(|index| {
+                let monitor = &self.monitors[*index];
+                let name = match monitor.disconnected {
+                    true => format!("{} (disconnected)", monitor.name),
+                    false => monitor.name.clone(),
+                };
                 let description = monitor.description.clone().unwrap_or_else(|| "No description".to_string());
                 let scale = monitor.scale.unwrap_or(1.0).to_string();
                 let enabled = monitor.enabled.to_string();
@@ -114,7 +178,7 @@ impl<'a> MonitorList<'a> {
                         .centered()
                         .style(
                             Style::default().fg(
-                                if enabled == "true" {Color::Green} else {Color::Red}
+                                if monitor.disconnected {Color::DarkGray} else if enabled == "true" {Color::Green} else {Color::Red}
                             )
                         ),
                     ),
@@ -124,18 +188,21 @@ impl<'a> MonitorList<'a> {
                     Cell::from(position),
                     Cell::from(scale),
                 ])
+                .style(if monitor.disconnected {Style::default().fg(Color::DarkGray)} else {Style::default()})
             }
             )
             .collect()
     }
     
     pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from(if self.monitors.len()>1 {" Displays "}else{" Display "}.white().bold());
+        let mut title = Line::from(if self.monitors.len()>1 {" Displays "}else{" Display "}.white().bold());
+        if self.modified {
+            title.push_span("[+] ".yellow().bold());
+        }
         let mut instructions_items = vec![];
 
         match self.mode {
             TUIMode::View => {
-                let selected_monitor = &self.monitors[self.selected_row.unwrap_or(0)];
                 instructions_items.push(" Up ".white());
                 instructions_items.push("<k> ".blue().bold());
                 instructions_items.push(" Down ".white());
@@ -146,13 +213,35 @@ impl<'a> MonitorList<'a> {
                 instructions_items.push("<r> ".blue().bold());
                 instructions_items.push(" Scale ".white());
                 instructions_items.push("<s> ".blue().bold());
-                if selected_monitor.enabled {
-                    instructions_items.push(" Disable ".white());
-                    instructions_items.push("<d> ".blue().bold());
-                } else {
-                    instructions_items.push(" Enable ".white());
-                    instructions_items.push("<e> ".blue().bold());
+                instructions_items.push(" Transform ".white());
+                instructions_items.push("<t> ".blue().bold());
+                instructions_items.push(" Mirror ".white());
+                instructions_items.push("<i> ".blue().bold());
+                instructions_items.push(" Profiles ".white());
+                instructions_items.push("<p> ".blue().bold());
+                instructions_items.push(" Arrange ".white());
+                instructions_items.push("<o> ".blue().bold());
+                instructions_items.push(" Backups ".white());
+                instructions_items.push("<b> ".blue().bold());
+                match self.order.get(self.selected_row.unwrap_or(0)).map(|index| &self.monitors[*index]) {
+                    Some(monitor) if monitor.enabled => {
+                        instructions_items.push(" Disable ".white());
+                        instructions_items.push("<d> ".blue().bold());
+                    },
+                    Some(_) => {
+                        instructions_items.push(" Enable ".white());
+                        instructions_items.push("<e> ".blue().bold());
+                    },
+                    None => {},
                 }
+                instructions_items.push(" Apply ".white());
+                instructions_items.push("<a> ".blue().bold());
+                instructions_items.push(" wlr-randr ".white());
+                instructions_items.push("<A> ".blue().bold());
+                instructions_items.push(" Undo ".white());
+                instructions_items.push("<u> ".blue().bold());
+                instructions_items.push(" Refresh ".white());
+                instructions_items.push("<R> ".blue().bold());
             },
 
             TUIMode::Resolution=> {
@@ -177,6 +266,14 @@ impl<'a> MonitorList<'a> {
                 instructions_items.push("<h> ".blue().bold());
                 instructions_items.push(" Right ".white());
                 instructions_items.push("<l> ".blue().bold());
+                instructions_items.push(" Place ".white());
+                instructions_items.push("<←↑↓→> ".blue().bold());
+                instructions_items.push(" Next to ".white());
+                instructions_items.push("<Tab> ".blue().bold());
+                instructions_items.push(" Align ".white());
+                instructions_items.push("<S-Tab> ".blue().bold());
+                instructions_items.push(" Snap ".white());
+                instructions_items.push("<s> ".blue().bold());
                 instructions_items.push(" Quit Move Mode ".white());
                 instructions_items.push("<Esc> ".blue().bold());
             },
@@ -190,12 +287,73 @@ impl<'a> MonitorList<'a> {
                 instructions_items.push(" Quit Scale Mode ".white());
                 instructions_items.push("<Esc> ".blue().bold());
             },
+            TUIMode::Transform => {
+                instructions_items.push(" Up ".white());
+                instructions_items.push("<k> ".blue().bold());
+                instructions_items.push(" Down ".white());
+                instructions_items.push("<j> ".blue().bold());
+                instructions_items.push(" Select ".white());
+                instructions_items.push("<Space> ".blue().bold());
+                instructions_items.push(" Quit Transform Mode ".white());
+                instructions_items.push("<Esc> ".blue().bold());
+            },
+            TUIMode::Mirror => {
+                instructions_items.push(" Up ".white());
+                instructions_items.push("<k> ".blue().bold());
+                instructions_items.push(" Down ".white());
+                instructions_items.push("<j> ".blue().bold());
+                instructions_items.push(" Select ".white());
+                instructions_items.push("<Space> ".blue().bold());
+                instructions_items.push(" Quit Mirror Mode ".white());
+                instructions_items.push("<Esc> ".blue().bold());
+            },
+            TUIMode::Arrange => {
+                instructions_items.push(" Up ".white());
+                instructions_items.push("<k> ".blue().bold());
+                instructions_items.push(" Down ".white());
+                instructions_items.push("<j> ".blue().bold());
+                instructions_items.push(" Arrange ".white());
+                instructions_items.push("<Space> ".blue().bold());
+                instructions_items.push(" Order ".white());
+                instructions_items.push("<o> ".blue().bold());
+                instructions_items.push(" Quit Arrange Mode ".white());
+                instructions_items.push("<Esc> ".blue().bold());
+            },
+            TUIMode::Backups => {
+                instructions_items.push(" Up ".white());
+                instructions_items.push("<k> ".blue().bold());
+                instructions_items.push(" Down ".white());
+                instructions_items.push("<j> ".blue().bold());
+                instructions_items.push(" Restore ".white());
+                instructions_items.push("<Space> ".blue().bold());
+                instructions_items.push(" Quit Backups Mode ".white());
+                instructions_items.push("<Esc> ".blue().bold());
+            },
+            TUIMode::Profiles => {
+                instructions_items.push(" Up ".white());
+                instructions_items.push("<k> ".blue().bold());
+                instructions_items.push(" Down ".white());
+                instructions_items.push("<j> ".blue().bold());
+                instructions_items.push(" Load ".white());
+                instructions_items.push("<Space> ".blue().bold());
+                instructions_items.push(" New ".white());
+                instructions_items.push("<n> ".blue().bold());
+                instructions_items.push(" Rename ".white());
+                instructions_items.push("<r> ".blue().bold());
+                instructions_items.push(" Delete ".white());
+                instructions_items.push("<d> ".blue().bold());
+                instructions_items.push(" Quit Profiles Mode ".white());
+                instructions_items.push("<Esc> ".blue().bold());
+            },
+            TUIMode::Apply | TUIMode::SaveConfirm | TUIMode::QuitConfirm | TUIMode::RandrConfirm => {},
         }
 
-        instructions_items.push(" Save ".white());
-        instructions_items.push("<w> ".blue().bold());
-        instructions_items.push(" Quit ".white());
-        instructions_items.push("<q> ".blue().bold());
+        if !matches!(self.mode, TUIMode::Apply | TUIMode::SaveConfirm | TUIMode::QuitConfirm | TUIMode::RandrConfirm) {
+            instructions_items.push(" Save ".white());
+            instructions_items.push("<w> ".blue().bold());
+            instructions_items.push(" Quit ".white());
+            instructions_items.push("<q> ".blue().bold());
+        }
 
         let instructions = Line::from(instructions_items);
 
@@ -259,21 +417,23 @@ mod tests {
         let mut list = MonitorList{
             state: TableState::default(),
             selected_row: Some(0),
+            modified: false,
             mode: TUIMode::View,
             monitors: &test_monitors(),
+            order: vec![0, 1],
         }; 
-        let mut buf = Buffer::empty(Rect::new(0, 0, 110, 7));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 210, 7));
         
         list.render(buf.area, &mut buf);
 
         let mut expected = Buffer::with_lines(vec![
-            "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Displays ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
-            "┃     name              description                      resolution             position         scale      ┃",
-            "┃                                                                                                            ┃",
-            "┃     Monitor 1         Description 1                    1920x1080              (0,0)            1          ┃",
-            "┃     Monitor 2         Description 2                    1280x720               (1920,0)         1.25       ┃",
-            "┃                                                                                                            ┃",
-            "┗━━━━━━━━━━ Up <k>  Down <j>  Move <m>  Resolution <r>  Scale <s>  Disable <d>  Save <w>  Quit <q> ━━━━━━━━━━┛",
+            "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Displays ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
+            "┃          name                             description                                                         resolution                                 position                        scale                ┃",
+            "┃                                                                                                                                                                                                                ┃",
+            "┃          Monitor 1                        Description 1                                                       1920x1080                                  (0,0)                           1                    ┃",
+            "┃          Monitor 2                        Description 2                                                       1280x720                                   (1920,0)                        1.25                 ┃",
+            "┃                                                                                                                                                                                                                ┃",
+            "┗━━ Up <k>  Down <j>  Move <m>  Resolution <r>  Scale <s>  Transform <t>  Mirror <i>  Profiles <p>  Arrange <o>  Backups <b>  Disable <d>  Apply <a>  wlr-randr <A>  Undo <u>  Refresh <R>  Save <w>  Quit <q> ━━┛",
         ]);
 
         let border_style = Style::new().fg(Color::Yellow);
@@ -287,64 +447,66 @@ mod tests {
         let row_style = Style::new();
 
         // first line : title
-        expected.set_style(Rect::new(0, 0, 50, 1), border_style);
-        expected.set_style(Rect::new(50, 0, 10, 1), title_style);
-        expected.set_style(Rect::new(60, 0, 50, 1), border_style);       
+        expected.set_style(Rect::new(0, 0, 100, 1), border_style);
+        expected.set_style(Rect::new(100, 0, 10, 1), title_style);
+        expected.set_style(Rect::new(110, 0, 100, 1), border_style);       
 
         // second line : header
         expected.set_style(Rect::new(0, 1, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 1, 108, 1), header_style);
-        expected.set_style(Rect::new(109, 1, 1, 1), border_style);
+        expected.set_style(Rect::new(1, 1, 208, 1), header_style);
+        expected.set_style(Rect::new(209, 1, 1, 1), border_style);
         
         // third line : empty
         expected.set_style(Rect::new(0, 2, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 2, 108, 1), empty_style);
-        expected.set_style(Rect::new(109, 2, 1, 1), border_style);
+        expected.set_style(Rect::new(1, 2, 208, 1), empty_style);
+        expected.set_style(Rect::new(209, 2, 1, 1), border_style);
          
         // fourth line : first row 
         expected.set_style(Rect::new(0, 3, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 3, 5, 1), connected_style);
-        expected.set_style(Rect::new(6, 3, 103, 1), row_style);
-        expected.set_style(Rect::new(109, 3, 1, 1), border_style);      
+        expected.set_style(Rect::new(1, 3, 10, 1), connected_style);
+        expected.set_style(Rect::new(11, 3, 198, 1), row_style);
+        expected.set_style(Rect::new(209, 3, 1, 1), border_style);      
 
         // fifth line : second row 
         expected.set_style(Rect::new(0, 4, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 4, 5, 1), disconnected_style);
-        expected.set_style(Rect::new(6, 4, 103, 1), row_style);
-        expected.set_style(Rect::new(109, 4, 1, 1), border_style);   
+        expected.set_style(Rect::new(1, 4, 10, 1), disconnected_style);
+        expected.set_style(Rect::new(11, 4, 198, 1), row_style);
+        expected.set_style(Rect::new(209, 4, 1, 1), border_style);   
          
         // fifth line : empty
         expected.set_style(Rect::new(0, 5, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 5, 108, 1), empty_style);
-        expected.set_style(Rect::new(109, 5, 1, 1), border_style);
+        expected.set_style(Rect::new(1, 5, 208, 1), empty_style);
+        expected.set_style(Rect::new(209, 5, 1, 1), border_style);
 
         // last line : instructions 
-        expected.set_style(Rect::new(0,6,  11, 1), border_style);
-        expected.set_style(Rect::new(11, 6, 4, 1), instructions_label_style);
-        expected.set_style(Rect::new(15, 6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(19,6, 6, 1), instructions_label_style);
-        expected.set_style(Rect::new(25,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(29,6, 6, 1), instructions_label_style);
-        expected.set_style(Rect::new(35,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(39,6, 12, 1), instructions_label_style);
-        expected.set_style(Rect::new(51,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(55,6, 7, 1), instructions_label_style);
-        expected.set_style(Rect::new(62,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(66,6, 9, 1), instructions_label_style);
-        expected.set_style(Rect::new(75,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(79,6, 6, 1), instructions_label_style);
-        expected.set_style(Rect::new(85,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(89,6, 6, 1), instructions_label_style);
-        expected.set_style(Rect::new(95,6, 4, 1), instructions_key_style);
-
-        expected.set_style(Rect::new(99,6, 11, 1), border_style);
+        let instructions = [
+            (" Up ", "<k> "),
+            (" Down ", "<j> "),
+            (" Move ", "<m> "),
+            (" Resolution ", "<r> "),
+            (" Scale ", "<s> "),
+            (" Transform ", "<t> "),
+            (" Mirror ", "<i> "),
+            (" Profiles ", "<p> "),
+            (" Arrange ", "<o> "),
+            (" Backups ", "<b> "),
+            (" Disable ", "<d> "),
+            (" Apply ", "<a> "),
+            (" wlr-randr ", "<A> "),
+            (" Undo ", "<u> "),
+            (" Refresh ", "<R> "),
+            (" Save ", "<w> "),
+            (" Quit ", "<q> "),
+        ];
+        let mut x = 3;
+        expected.set_style(Rect::new(0, 6, x, 1), border_style);
+        for (label, key) in instructions {
+            expected.set_style(Rect::new(x, 6, label.len() as u16, 1), instructions_label_style);
+            x += label.len() as u16;
+            expected.set_style(Rect::new(x, 6, key.len() as u16, 1), instructions_key_style);
+            x += key.len() as u16;
+        }
+        expected.set_style(Rect::new(x, 6, 210 - x, 1), border_style);
 
         assert_eq!(buf, expected);
     }
@@ -1,5 +1,7 @@
 use std::io;
-use crossterm::event::{self,Event,KeyCode,KeyEvent,KeyEventKind};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use crossterm::event::{self,Event,KeyCode,KeyEvent,KeyEventKind,KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -7,61 +9,171 @@ use ratatui::{
     DefaultTerminal,Frame,
 };
 use ratatui::prelude::*;
+mod apply;
+mod arrange;
+mod backend;
+mod backup;
+mod cli;
+mod daemon;
+mod diff;
+mod error;
+mod history;
+mod kanshi;
+mod hyprland;
+mod layout;
 mod list;
 mod map;
+mod mirror;
 mod monitor;
+mod niri;
+mod profile;
+mod problems;
+mod profiles;
+mod quit;
+mod randr;
+mod refresh;
+mod restore;
 mod resolutions;
 mod utils;
+mod save;
+mod status;
+mod sway;
 mod scale;
+mod transform;
+mod undo;
+mod writer;
 mod configuration;
 mod test_utils;
 
+use apply::{ApplyConfirm, PendingApply};
+use arrange::Arrange;
+use backend::{CommandRunner, DisplayBackend};
+use history::History;
 use list::MonitorList;
 use map::Map;
-use monitor::Monitor;
+use mirror::Mirror;
+use monitor::{Alignment, Monitor, SnapGuide};
+use problems::Problems;
+use profiles::{ProfileInput, Profiles};
+use quit::QuitConfirm;
+use randr::RandrConfirm;
+use restore::{Backup, RestorePicker};
 
 use resolutions::Resolutions; 
+use save::SaveConfirm;
 use scale::Scale;
+use status::{Status, StatusBar};
+use transform::Transform;
+use undo::UndoHistory;
 use utils::TUIMode;
 use configuration::Configuration;
+use error::{Error, ErrorPopup};
+use diff::DiffLine;
+use cli::Command;
 
-fn main() -> io::Result<()> {
-    let mut terminal = ratatui::init();
-    let app_result = App::default().run(&mut terminal);
-    ratatui::restore();
-    app_result
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match Command::parse(&args) {
+        Ok(Command::Tui) => {
+            let mut terminal = ratatui::init();
+            let app_result = App::default().run(&mut terminal);
+            ratatui::restore();
+            app_result
+        },
+        Ok(Command::Daemon) => daemon::run(),
+        Ok(command) => return cli::run(command),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(e.exit_code());
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
 #[derive(Debug, Default)]
 struct App {
     exit:bool,
     config: Configuration,
+    history: History,
+    backend: Box<dyn DisplayBackend>,
+    runner: Box<dyn CommandRunner>,
     monitors: Vec<Monitor>,
+    saved_monitors: Vec<Monitor>,
+    undo: UndoHistory,
     selected_monitor: usize,
+    list_order: Vec<usize>,
     selected_resolution : usize,
     selected_scale: usize,
+    selected_transform: usize,
+    selected_mirror: usize,
+    selected_arrangement: usize,
+    arrange_by_list: bool,
+    mirror_error: Option<String>,
     mode: TUIMode,
+    free_move: bool,
+    snap_guide: Option<SnapGuide>,
+    alignment: Alignment,
+    reference_monitor: Option<usize>,
+    pending_apply: Option<PendingApply>,
+    save_diff: Vec<DiffLine>,
+    save_scroll: u16,
+    area: Rect,
+    quit_after_save: bool,
+    selected_profile: usize,
+    backups: Vec<Backup>,
+    selected_backup: usize,
+    profile_input: Option<ProfileInput>,
+    error: Option<Error>,
+    status: Status,
+    next_refresh: Option<Instant>,
 }
 
 impl App{
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        self.monitors = Monitor::get_monitors();
-        self.selected_resolution= 0;
-        self.selected_monitor= 0;
-        self.config = Configuration::get();
+        let config = Configuration::get();
+        self.config = ErrorPopup::report(self, config).unwrap_or_else(Configuration::fallback);
+        let history = History::load(&self.config);
+        self.history = ErrorPopup::report(self, history).unwrap_or_default();
+        self.backend = self.config.backend.create();
+        let loaded = self.load();
+        ErrorPopup::report(self, loaded);
 
         while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
+            self.area = terminal.draw(|frame| self.draw(frame))?.area;
             self.handle_events()?;
+            ApplyConfirm::tick(self);
+            refresh::tick(self, Instant::now());
+            self.status.tick(Instant::now());
         }
         Ok(())
     }
 
+    fn load(&mut self) -> error::Result<()> {
+        self.monitors = self.backend.get_monitors().map_err(Error::Backend)?;
+        self.config.merge_monitors_config(&mut self.monitors)?;
+        self.history.remember(&self.monitors);
+        self.undo.clear();
+        self.saved_monitors = self.monitors.clone();
+        self.selected_resolution= 0;
+        self.selected_monitor= 0;
+        self.list_order.clear();
+        Ok(())
+    }
+
     fn draw(&self, frame: &mut Frame){
         frame.render_widget(self,frame.area());
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
+        if !event::poll(Duration::from_millis(250))? {
+            return Ok(());
+        }
         match event::read()? {
             Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
                 self.handle_key_event(key_event)
@@ -72,29 +184,83 @@ impl App{
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.error.is_some() {
+            return ErrorPopup::handle_events(self,key_event);
+        }
+        // nothing to edit when the displays could not be read
+        if self.monitors.is_empty() && !matches!(key_event.code, KeyCode::Char('q' | 'R')) {
+            return;
+        }
+        match self.mode {
+            TUIMode::Apply => return ApplyConfirm::handle_events(self,key_event),
+            TUIMode::SaveConfirm => return SaveConfirm::handle_events(self,key_event),
+            TUIMode::QuitConfirm => return QuitConfirm::handle_events(self,key_event),
+            TUIMode::RandrConfirm => return RandrConfirm::handle_events(self,key_event),
+            _ => {}
+        }
+        if self.profile_input.is_some() {
+            Profiles::handle_events(self,key_event);
+            return;
+        }
+        let before = self.monitors.clone();
+        // nudges in move mode are undone together
+        let nudge = self.mode == TUIMode::Move
+            && matches!(key_event.code, KeyCode::Char('h' | 'j' | 'k' | 'l' | 'H' | 'J' | 'K' | 'L'));
         match key_event.code {
-            KeyCode::Char('q') => self.exit(),
-            KeyCode::Char('w') => self.write(), 
+            KeyCode::Char('q') => QuitConfirm::request(self),
+            KeyCode::Char('w') => SaveConfirm::request(self),
+            KeyCode::Char('a') => ApplyConfirm::start(self),
+            KeyCode::Char('A') => RandrConfirm::request(self),
+            KeyCode::Char('R') => return refresh::run(self, true),
+            KeyCode::Char('u') => {
+                match self.undo.undo(&mut self.monitors) {
+                    true => self.status.info("Undone"),
+                    false => self.status.warning("Nothing to undo"),
+                }
+                self.snap_guide = None;
+                return;
+            },
+            KeyCode::Char('r') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                match self.undo.redo(&mut self.monitors) {
+                    true => self.status.info("Redone"),
+                    false => self.status.warning("Nothing to redo"),
+                }
+                self.snap_guide = None;
+                return;
+            },
             _ => {
                 match self.mode {
                     TUIMode::View => MonitorList::handle_events(self,key_event),
                     TUIMode::Move => Map::handle_events(self,key_event),
                     TUIMode::Resolution=> Resolutions::handle_events(self,key_event),
                     TUIMode::Scale => Scale::handle_events(self,key_event), 
+                    TUIMode::Transform => Transform::handle_events(self,key_event),
+                    TUIMode::Mirror => Mirror::handle_events(self,key_event),
+                    TUIMode::Arrange => Arrange::handle_events(self,key_event),
+                    TUIMode::Backups => RestorePicker::handle_events(self,key_event),
+                    TUIMode::Profiles => Profiles::handle_events(self,key_event),
+                    TUIMode::Apply | TUIMode::SaveConfirm | TUIMode::QuitConfirm | TUIMode::RandrConfirm => {}
                 }
             }
         }
+        if self.monitors != before {
+            self.undo.record(before, nudge);
+        } else if !nudge {
+            self.undo.break_coalescing();
+        }
     }
     
-    fn exit(&mut self) {
-        self.exit = true;
+    // the same settings the quit confirmation lists, neither the order nor the live state is an edit
+    fn is_modified(&self) -> bool {
+        !QuitConfirm::diff(&self.saved_monitors, &self.monitors).is_empty()
     }
     
-    fn write(&mut self) {
-        Monitor::save_hyprland_config(
-            &self.config.monitors_config_path,
-            &self.monitors
-        ).expect("Failed to save Hyprland config");
+    fn write(&mut self) -> error::Result<()> {
+        writer::save(&self.config, &self.monitors)?;
+        self.saved_monitors = self.monitors.clone();
+        self.history.remember(&Monitor::connected(&self.monitors));
+        self.history.save()?;
+        Ok(())
     }         
 }
 
@@ -103,22 +269,36 @@ impl Widget for &App {
     fn render(self,area: Rect, buf: &mut Buffer) {
         let mut monitor_list = MonitorList::new(
             &self.monitors,
+            &self.list_order,
             self.mode,
             Some(self.selected_monitor), 
+            self.is_modified(),
         );
 
         let canvas = Map {
             mode: self.mode,
             selected: self.selected_monitor,
             monitors: &self.monitors,
+            guide: self.snap_guide,
+            alignment: self.alignment,
+            reference: Map::reference(self),
         };
+        let problems = Problems::new(&self.monitors);
+        let status_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(0),
+                Constraint::Length(problems.height()),
+                Constraint::Length(1),
+            ])
+            .split(area);
         let outer_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
                 Constraint::Percentage(70),
                 Constraint::Percentage(30),
             ])
-            .split(area);
+            .split(status_layout[0]);
 
         match self.mode {
             TUIMode::Resolution=> {
@@ -149,19 +329,216 @@ impl Widget for &App {
                 canvas.render(inner_top_layout[0], buf);
                 scale.render(inner_top_layout[1], buf);
             }
+            TUIMode::Transform => {
+                let mut transform = Transform::new(self.selected_transform);
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(85),
+                        Constraint::Percentage(15),
+                    ])
+                    .split(outer_layout[0]);
+                canvas.render(inner_top_layout[0], buf);
+                transform.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Mirror => {
+                let mut mirror = Mirror::new(
+                    &self.monitors,
+                    self.selected_monitor,
+                    self.mirror_error.as_deref(),
+                    Some(self.selected_mirror),
+                );
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(30),
+                    ])
+                    .split(outer_layout[0]);
+                canvas.render(inner_top_layout[0], buf);
+                mirror.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Arrange => {
+                let mut arrange = Arrange::new(self.selected_arrangement, self.arrange_by_list);
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(85),
+                        Constraint::Percentage(15),
+                    ])
+                    .split(outer_layout[0]);
+                canvas.render(inner_top_layout[0], buf);
+                arrange.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Backups => {
+                let mut backups = RestorePicker::new(&self.backups, Some(self.selected_backup));
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(40),
+                        Constraint::Percentage(60),
+                    ])
+                    .split(outer_layout[0]);
+                canvas.render(inner_top_layout[0], buf);
+                backups.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Profiles => {
+                let mut profiles = Profiles::new(
+                    &self.config.profiles,
+                    &self.monitors,
+                    self.profile_input.as_ref(),
+                    Some(self.selected_profile),
+                );
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(30),
+                    ])
+                    .split(outer_layout[0]);
+                canvas.render(inner_top_layout[0], buf);
+                profiles.render(inner_top_layout[1], buf);
+            }
             _ => {
                 canvas.render(outer_layout[0], buf);
             }
         }
         monitor_list.render(outer_layout[1], buf);
+        problems.render(status_layout[1], buf);
+        StatusBar::new(&self.status, self.undo.depth()).render(status_layout[2], buf);
+
+        if let Some(pending) = &self.pending_apply {
+            ApplyConfirm::new(pending).render(ApplyConfirm::popup_area(area), buf);
+        }
+        if self.mode == TUIMode::SaveConfirm {
+            let save = SaveConfirm::new(&self.monitors, &self.save_diff, self.save_scroll);
+            save.render(save.popup_area(area), buf);
+        }
+        if self.mode == TUIMode::RandrConfirm {
+            let randr = RandrConfirm::new(&self.monitors);
+            randr.render(randr.popup_area(area), buf);
+        }
+        if self.mode == TUIMode::QuitConfirm {
+            let quit = QuitConfirm::new(&self.saved_monitors, &self.monitors);
+            quit.render(quit.popup_area(area), buf);
+        }
+        if let Some(error) = &self.error {
+            let popup = ErrorPopup::new(error);
+            popup.render(popup.popup_area(area), buf);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::writer::{ConfigFormat, Target};
     use crate::test_utils::tests::test_monitors;
+    use crate::monitor::Position;
+    use crate::profiles::ProfileInputKind;
+    use crate::backend::Hyprctl;
+    use crate::backend::fake::{FakeBackend, FakeRunner};
+
+    #[test]
+    fn load_and_apply_with_backend() -> io::Result<()> {
+        let mut app = App{
+            backend: Box::new(FakeBackend::new(test_monitors())),
+            ..Default::default()
+        };
+
+        app.load()?;
+        assert_eq!(app.monitors.len(), 2);
+        assert_eq!(app.selected_monitor, 0);
+
+        app.monitors[0].move_horizontal(100);
+        app.backend.apply(&app.monitors)?;
+        let applied = app.backend.get_monitors()?;
+        assert_eq!(applied[0].position.clone().unwrap().x, 100);
+
+        // backend failures are returned to the caller
+        let mut backend = FakeBackend::new(test_monitors());
+        backend.error = Some("wlr-randr not found".to_string());
+        app.backend = Box::new(backend);
+        assert!(matches!(app.load(), Err(Error::Backend(_))));
+
+        Ok(())
+    }
    
+    #[test]
+    fn apply_confirm_and_revert() -> io::Result<()> {
+        let runner = FakeRunner::default()
+            .with_response("monitors", include_str!("../tests/fixtures/hyprctl_monitors_docked.json"))
+            .with_response("--batch", "ok\nok\n");
+        let calls = runner.calls.clone();
+        let mut app = App{
+            backend: Box::new(Hyprctl::new(Box::new(runner))),
+            ..Default::default()
+        };
+        app.load()?;
+
+        app.handle_key_event(KeyCode::Char('m').into());
+        app.handle_key_event(KeyCode::Char('H').into());
+        app.handle_key_event(KeyCode::Esc.into());
+        app.handle_key_event(KeyCode::Char('a').into());
+        assert_eq!(app.mode, TUIMode::Apply);
+        assert!(calls.borrow().last().unwrap()[2].starts_with("keyword monitor desc:BOE 0x0BCA, 2256x1504@60, -100x0, 1.5"));
+
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(!app.exit);
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(app.pending_apply.is_none());
+        assert_eq!(app.monitors[0].position.clone().unwrap().x, -100);
+
+        app.handle_key_event(KeyCode::Char('a').into());
+        app.pending_apply.as_mut().unwrap().deadline = Instant::now();
+        ApplyConfirm::tick(&mut app);
+        assert_eq!(app.mode, TUIMode::View);
+        // the compositor is reverted, the edited layout is kept
+        assert_eq!(app.monitors[0].position.clone().unwrap().x, -100);
+        assert!(calls.borrow().last().unwrap()[2].contains(", 0x0, 1.5"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_with_wlr_randr() {
+        let runner = FakeRunner::default();
+        let calls = runner.calls.clone();
+        let mut app = App{
+            backend: Box::new(FakeBackend::new(test_monitors())),
+            runner: Box::new(runner),
+            monitors: test_monitors(),
+            saved_monitors: test_monitors(),
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        app.handle_key_event(KeyCode::Esc.into());
+        app.handle_key_event(KeyCode::Char('A').into());
+        assert_eq!(app.mode, TUIMode::RandrConfirm);
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(calls.borrow().is_empty());
+
+        app.handle_key_event(KeyCode::Char('A').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert_eq!(app.mode, TUIMode::Apply);
+        assert_eq!(
+            calls.borrow()[0].join(" "),
+            format!("wlr-randr --output Monitor 1 --on --mode 1920x1080@60Hz --pos 0,0 --scale {} --transform normal --output Monitor 2 --off", app.monitors[0].scale.unwrap())
+        );
+        let edited = app.monitors.clone();
+        assert_ne!(edited, test_monitors());
+        app.pending_apply.as_mut().unwrap().deadline = Instant::now();
+        ApplyConfirm::tick(&mut app);
+        // the unsaved scale survives the revert
+        assert_eq!(app.monitors, edited);
+        assert!(app.is_modified());
+        assert_eq!(calls.borrow().len(), 1);
+    }
+
     #[test]
     fn handle_mode_view_key_event() -> io::Result<()> {
         let mut app = App{
@@ -201,6 +578,8 @@ mod tests {
         assert_eq!(app.mode, TUIMode::View);
 
         app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('d').into());
         assert!(app.exit);
 
         Ok(())
@@ -235,11 +614,242 @@ mod tests {
         assert_eq!(monitor.position.unwrap().x, 0);
 
         app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('d').into());
         assert!(app.exit);
 
         Ok(())
     }       
     #[test]
+    fn handle_snapping_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.0);
+        monitors[1].position = Some(Position { x: 1960, y: 0 });
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('h').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 1920, y: 0 }));
+        assert!(app.snap_guide.is_some());
+
+        app.handle_key_event(KeyCode::Char('s').into());
+        assert!(app.free_move);
+        assert!(app.snap_guide.is_none());
+
+        app.handle_key_event(KeyCode::Char('h').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 1910, y: 0 }));
+        app.handle_key_event(KeyCode::Char('l').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 1920, y: 0 }));
+        assert!(app.snap_guide.is_none());
+    }
+    #[test]
+    fn handle_placement_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Left.into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: -1024, y: 0 }));
+
+        app.handle_key_event(KeyCode::BackTab.into());
+        assert_eq!(app.alignment, Alignment::Center);
+        app.handle_key_event(KeyCode::Down.into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 448, y: 1080 }));
+
+        // the reference is chosen among the other outputs
+        let mut third = app.monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.position = Some(Position { x: 1920, y: 0 });
+        app.monitors.push(third);
+        app.handle_key_event(KeyCode::Tab.into());
+        assert_eq!(app.reference_monitor, Some(2));
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 2368, y: -576 }));
+        app.handle_key_event(KeyCode::Tab.into());
+        assert_eq!(app.reference_monitor, Some(0));
+
+        // errors of the placement go to the status bar
+        app.monitors[0].modes.clear();
+        app.handle_key_event(KeyCode::Right.into());
+        assert_eq!(app.status.current().map(|message| message.text.as_str()), Some("Monitor 1 has no position"));
+    }
+    #[test]
+    fn handle_mode_arrange_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = Some(Position { x: -3000, y: 500 });
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('o').into());
+        assert_eq!(app.mode, TUIMode::Arrange);
+
+        // row ordered by position
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(app.monitors[0].position, Some(Position { x: 1024, y: 0 }));
+
+        // column ordered by list, after moving the first monitor down the list
+        app.handle_key_event(KeyCode::Esc.into());
+        let before = app.monitors.clone();
+        app.handle_key_event(KeyCode::Char('J').into());
+        assert_eq!(app.list_order, vec![1, 0]);
+        assert_eq!(app.selected_monitor, 0);
+        // the monitors and the written config keep their order
+        assert_eq!(app.monitors, before);
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_monitor, 1);
+        app.handle_key_event(KeyCode::Char('o').into());
+        app.handle_key_event(KeyCode::Char('o').into());
+        assert!(app.arrange_by_list);
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 576 }));
+    }
+    #[test]
+    fn handle_enable_key_event() {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 1,
+            ..Default::default()
+        };
+
+        // never seen before: flush to the right of the layout, keeping its scale
+        app.monitors[1].position = Some(Position { x: 0, y: 0 });
+        app.handle_key_event(KeyCode::Char('e').into());
+        assert!(app.monitors[1].enabled);
+        assert_eq!(app.monitors[1].position, Some(Position { x: 1920, y: 0 }));
+        assert_eq!(app.monitors[1].scale, Some(1.25));
+
+        // disabled then enabled again: back where it was
+        app.monitors[1].position = Some(Position { x: -1024, y: 100 });
+        app.monitors[1].scale = Some(1.0);
+        app.handle_key_event(KeyCode::Char('d').into());
+        app.monitors[1].position = Some(Position { x: 0, y: 0 });
+        app.handle_key_event(KeyCode::Char('e').into());
+        assert_eq!(app.monitors[1].position, Some(Position { x: -1024, y: 100 }));
+        assert_eq!(app.monitors[1].scale, Some(1.0));
+
+        // the first monitor is remembered when loaded
+        app.history.remember(&test_monitors());
+        app.selected_monitor = 0;
+        app.handle_key_event(KeyCode::Char('d').into());
+        app.monitors[0].position = Some(Position { x: 500, y: 500 });
+        app.handle_key_event(KeyCode::Char('e').into());
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 0 }));
+    }
+    #[test]
+    fn handle_undo_key_event() {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+        let redo = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+
+        app.handle_key_event(KeyCode::Char('m').into());
+        for _ in 0..5 {
+            app.handle_key_event(KeyCode::Char('l').into());
+        }
+        app.handle_key_event(KeyCode::Char('J').into());
+        app.handle_key_event(KeyCode::Esc.into());
+        app.handle_key_event(KeyCode::Char('d').into());
+        assert_eq!(app.undo.depth(), (2, 0));
+
+        app.handle_key_event(KeyCode::Char('u').into());
+        assert!(app.monitors[0].enabled);
+        assert_eq!(app.monitors[0].position, Some(Position { x: 50, y: 100 }));
+        app.handle_key_event(KeyCode::Char('u').into());
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(app.undo.depth(), (0, 2));
+
+        app.handle_key_event(redo);
+        app.handle_key_event(redo);
+        assert!(!app.monitors[0].enabled);
+        assert_eq!(app.monitors[0].position, Some(Position { x: 50, y: 100 }));
+        assert_eq!(app.mode, TUIMode::View);
+    }
+    #[test]
+    fn handle_quit_key_event() {
+        let mut app = App{
+            monitors: test_monitors(),
+            saved_monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+        assert!(!app.is_modified());
+
+        app.handle_key_event(KeyCode::Char('d').into());
+        assert!(app.is_modified());
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('c').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(!app.exit);
+
+        app.handle_key_event(KeyCode::Char('u').into());
+        assert!(!app.is_modified());
+        app.monitors.swap(0, 1);
+        assert!(!app.is_modified());
+        app.monitors[0].focused = true;
+        app.monitors[0].dpms_status = Some(false);
+        assert!(!app.is_modified());
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(app.exit);
+    }
+    #[test]
+    fn save_on_quit() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("display-tui-quit-{}", std::process::id()));
+        let mut app = App{
+            monitors: test_monitors(),
+            saved_monitors: test_monitors(),
+            selected_monitor: 1,
+            ..Default::default()
+        };
+        app.config.monitors_config_path = dir.join("monitors.conf").to_string_lossy().to_string();
+
+        // enabled on top of the first monitor
+        app.handle_key_event(KeyCode::Char('e').into());
+        app.monitors[1].position = Some(Position { x: 0, y: 0 });
+        app.handle_key_event(KeyCode::Char('q').into());
+        app.handle_key_event(KeyCode::Char('s').into());
+        assert_eq!(app.mode, TUIMode::SaveConfirm);
+        assert!(!app.save_diff.is_empty());
+        // scrolling stops once the last line reaches the bottom of the popup
+        app.area = Rect::new(0, 0, 100, 8);
+        let max = SaveConfirm::new(&app.monitors, &app.save_diff, 0).max_scroll(app.area);
+        assert!(max > 0);
+        for _ in 0..50 {
+            app.handle_key_event(KeyCode::Char('j').into());
+        }
+        assert_eq!(app.save_scroll, max);
+        app.handle_key_event(KeyCode::Char('n').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(!app.exit && !dir.join("monitors.conf").exists());
+
+        app.handle_key_event(KeyCode::Char('q').into());
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(app.exit);
+        assert!(dir.join("monitors.conf").exists());
+
+        std::fs::remove_dir_all(&dir)
+    }
+    #[test]
     fn handle_mode_resolution_key_event() -> io::Result<()> {
         let mut app = App{
             monitors: test_monitors(),
@@ -259,9 +869,11 @@ mod tests {
 
         app.handle_key_event(KeyCode::Char(' ').into());
         let monitor = app.monitors[0].clone();
-        assert_eq!(monitor.modes[0].current, true);
+        assert!(monitor.modes[0].current);
 
         app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('d').into());
         assert!(app.exit);
 
         Ok(())
@@ -290,8 +902,397 @@ mod tests {
         assert_eq!(monitor.scale, Some(0.5));
 
         app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('d').into());
         assert!(app.exit);
 
         Ok(())
     }       
+
+    #[test]
+    fn handle_mode_profiles_key_event() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-profiles-{}.json", std::process::id()));
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        // without a configuration file the profile cannot be saved
+        app.handle_key_event(KeyCode::Char('p').into());
+        app.handle_key_event(KeyCode::Char('n').into());
+        app.handle_key_event(KeyCode::Char('x').into());
+        app.handle_key_event(KeyCode::Enter.into());
+        assert!(matches!(app.error, Some(Error::Io(_))));
+        // the profile that was not saved is not kept either
+        assert!(app.config.profiles.is_empty());
+        app.error = None;
+        app.mode = TUIMode::View;
+        app.config.path = Some(path.clone());
+
+        app.handle_key_event(KeyCode::Char('p').into());
+        assert_eq!(app.mode, TUIMode::Profiles);
+
+        app.handle_key_event(KeyCode::Char('n').into());
+        for c in "desk".chars() {
+            app.handle_key_event(KeyCode::Char(c).into());
+        }
+        app.handle_key_event(KeyCode::Enter.into());
+        assert!(app.profile_input.is_none());
+        assert_eq!(app.config.profiles["desk"].len(), 2);
+
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Backspace.into());
+        app.handle_key_event(KeyCode::Char('q').into());
+        app.handle_key_event(KeyCode::Enter.into());
+        assert!(!app.exit);
+        assert!(app.config.profiles.contains_key("desq"));
+        assert!(!app.config.profiles.contains_key("desk"));
+
+        // an existing profile is not replaced
+        app.config.profiles.get_mut("desq").unwrap().pop();
+        app.handle_key_event(KeyCode::Char('n').into());
+        for c in "desq".chars() {
+            app.handle_key_event(KeyCode::Char(c).into());
+        }
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(app.profile_input.as_ref().and_then(|input| input.error.as_deref()), Some("desq already exists"));
+        assert_eq!(app.config.profiles["desq"].len(), 1);
+        app.handle_key_event(KeyCode::Esc.into());
+        assert!(app.profile_input.is_none());
+        app.config.profiles.insert("desq".to_string(), profile::from_monitors(&app.monitors));
+
+        app.monitors[0].position = Some(Position { x: 500, y: 500 });
+        app.monitors[1].enabled = true;
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert!(!app.monitors[1].enabled);
+
+        // deleting asks first and is rolled back when it cannot be saved
+        app.handle_key_event(KeyCode::Char('d').into());
+        assert_eq!(app.profile_input.as_ref().map(|input| input.kind), Some(ProfileInputKind::Delete));
+        app.handle_key_event(KeyCode::Char('n').into());
+        assert!(app.profile_input.is_none());
+        assert!(app.config.profiles.contains_key("desq"));
+        app.config.path = None;
+        app.handle_key_event(KeyCode::Char('d').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(matches!(app.error, Some(Error::Io(_))));
+        assert!(app.config.profiles.contains_key("desq"));
+        app.error = None;
+        app.config.path = Some(path.clone());
+        app.handle_key_event(KeyCode::Char('d').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(app.config.profiles.is_empty());
+
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        std::fs::remove_file(&path)
+    }
+
+    #[test]
+    fn handle_mode_transform_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('t').into());
+        assert_eq!(app.mode, TUIMode::Transform);
+
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_transform, 1);
+        app.handle_key_event(KeyCode::Char(' ').into());
+        let monitor = &app.monitors[0];
+        assert_eq!(monitor.transform, 1);
+        assert_eq!(monitor.logical_size(), Some((1080.0, 1920.0)));
+        assert!(monitor.to_hyprland_config().unwrap().ends_with(", 0x0, 1, transform, 1"));
+
+        let canvas = Monitor::get_monitors_canvas(&app.monitors, &Rect::new(0, 0, 100, 100));
+        assert_eq!(canvas.top, 1920 + 50);
+
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_mirror_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('i').into());
+        assert_eq!(app.mode, TUIMode::Mirror);
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.mirror_error.as_deref(), Some("Cannot mirror disabled output Monitor 2"));
+        assert_eq!(app.monitors[0].mirror_of, None);
+        app.handle_key_event(KeyCode::Esc.into());
+        assert!(app.mirror_error.is_none());
+
+        app.handle_key_event(KeyCode::Char('k').into());
+        app.handle_key_event(KeyCode::Char('e').into());
+        app.handle_key_event(KeyCode::Char('i').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[1].mirror_of.as_deref(), Some("Monitor 1"));
+        assert!(app.monitors[1].to_hyprland_config().unwrap().ends_with(", mirror, Monitor 1"));
+        app.handle_key_event(KeyCode::Esc.into());
+
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char('i').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.mirror_error.as_deref(), Some("Monitor 2 already mirrors Monitor 1"));
+        assert_eq!(app.monitors[0].mirror_of, None);
+
+        app.handle_key_event(KeyCode::Char('k').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(app.mirror_error.is_none());
+        assert_eq!(app.monitors[0].mirror_of, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_backups_key_event() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("display-tui-backups-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("monitors.conf");
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                monitors_config_path: path.to_string_lossy().to_string(),
+                backups: 3,
+                targets: vec![Target { format: ConfigFormat::WlrRandr, path: dir.join("layout.sh").to_string_lossy().to_string() }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for x in [0, 10, 20] {
+            app.monitors[0].position = Some(Position { x, y: 0 });
+            app.handle_key_event(KeyCode::Char('w').into());
+            app.handle_key_event(KeyCode::Char('y').into());
+            std::thread::sleep(Duration::from_millis(2));
+        }
+
+        app.handle_key_event(KeyCode::Char('b').into());
+        assert_eq!(app.mode, TUIMode::Backups);
+        // the backups of every written file are listed
+        assert_eq!(app.backups.len(), 4);
+        assert!(app.backups[0].content.contains("10x0"));
+        assert_eq!(app.backups[2].target.format, ConfigFormat::WlrRandr);
+
+        // an edit the restored file does not set stays unsaved
+        app.monitors[1].scale = Some(2.0);
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(std::fs::read_to_string(&path)?.contains("desc:Some Description 1, 1920x1080@60, 0x0, 1"));
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(QuitConfirm::diff(&app.saved_monitors, &app.monitors), vec!["Monitor 2: scale 1.25 → 2"]);
+        app.monitors[1].scale = Some(1.25);
+        assert!(!app.is_modified());
+        assert_eq!(backup::list(&path)?.len(), 3);
+
+        // restoring another file leaves the layout alone
+        app.monitors[0].position = Some(Position { x: 50, y: 0 });
+        app.handle_key_event(KeyCode::Char('b').into());
+        app.selected_backup = 4;
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(std::fs::read_to_string(dir.join("layout.sh"))?.contains("--pos 0,0"));
+        assert_eq!(app.monitors[0].position, Some(Position { x: 50, y: 0 }));
+
+        std::fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn handle_save_invalid_layout_key_event() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("display-tui-save-{}.conf", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 1,
+            config: Configuration {
+                monitors_config_path: path.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('e').into());
+        assert!(layout::invalid_monitors(&app.monitors).is_empty());
+        app.monitors[1].position = Some(Position { x: 0, y: 0 });
+        assert_eq!(layout::invalid_monitors(&app.monitors), vec![0, 1]);
+
+        app.handle_key_event(KeyCode::Char('w').into());
+        assert_eq!(app.mode, TUIMode::SaveConfirm);
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(!app.exit);
+        app.handle_key_event(KeyCode::Char('n').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(!path.exists());
+
+        app.handle_key_event(KeyCode::Char('w').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert_eq!(app.mode, TUIMode::View);
+        assert_eq!(std::fs::read_to_string(&path)?.lines().count(), 2);
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn report_backend_errors() {
+        let mut app = App{
+            backend: Box::new(FakeBackend {
+                error: Some("wlr-randr not found".to_string()),
+                ..FakeBackend::new(test_monitors())
+            }),
+            ..Default::default()
+        };
+        let loaded = app.load();
+        assert!(ErrorPopup::report(&mut app, loaded).is_none());
+        assert_eq!(app.error.as_ref().unwrap().to_string(), "Display backend error: wlr-randr not found");
+        assert!(app.monitors.is_empty());
+
+        // any key closes the popup, the empty layout ignores the other keys
+        app.handle_key_event(KeyCode::Char('m').into());
+        assert!(app.error.is_none());
+        app.handle_key_event(KeyCode::Char('m').into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        app.monitors = test_monitors();
+        app.saved_monitors = test_monitors();
+        app.handle_key_event(KeyCode::Char('a').into());
+        assert!(matches!(app.error, Some(Error::Backend(_))));
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(app.pending_apply.is_none());
+
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(app.error.is_none());
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(app.exit);
+    }
+
+    #[test]
+    fn report_save_errors() {
+        // the parent of the monitors config is a dangling link, it cannot be created
+        let dir = std::env::temp_dir().join(format!("display-tui-missing-{}", std::process::id()));
+        std::os::unix::fs::symlink(dir.with_extension("nowhere"), &dir).unwrap();
+        let mut app = App{
+            monitors: test_monitors(),
+            saved_monitors: test_monitors(),
+            config: Configuration {
+                monitors_config_path: dir.join("monitors.conf").to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('d').into());
+        app.handle_key_event(KeyCode::Char('w').into());
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(matches!(app.error, Some(Error::Io(_))));
+        assert!(app.is_modified());
+
+        app.handle_key_event(KeyCode::Esc.into());
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert_eq!(app.mode, TUIMode::QuitConfirm);
+        app.handle_key_event(KeyCode::Char('s').into());
+        assert_eq!(app.mode, TUIMode::SaveConfirm);
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(app.error.is_some());
+        assert!(!app.exit && !app.quit_after_save);
+        assert!(!dir.exists());
+        std::fs::remove_file(&dir).unwrap();
+    }
+
+    #[test]
+    fn push_status_messages() {
+        let mut app = App{
+            monitors: test_monitors(),
+            saved_monitors: test_monitors(),
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('u').into());
+        app.handle_key_event(KeyCode::Char('m').into());
+        app.handle_key_event(KeyCode::Esc.into());
+        app.handle_key_event(KeyCode::Char('w').into());
+        app.handle_key_event(KeyCode::Char('n').into());
+
+        let mut messages = vec![];
+        let mut now = Instant::now();
+        while let Some(message) = app.status.current() {
+            messages.push((message.level, message.text.clone()));
+            now += Duration::from_secs(60);
+            app.status.tick(now);
+        }
+        // only the feedback of the last key is left
+        assert_eq!(messages, vec![
+            (status::Level::Info, "Save cancelled".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn refresh_hotplugged_outputs() {
+        let mut projector = test_monitors()[1].clone();
+        projector.name = "HDMI-A-1".to_string();
+        projector.serial = Some("3".to_string());
+        let mut app = App{
+            backend: Box::new(FakeBackend::new(test_monitors())),
+            config: Configuration {
+                monitors_config_path: std::env::temp_dir().join("display-tui-refresh-missing.conf").to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.load().unwrap();
+        app.undo.record(app.monitors.clone(), false);
+        app.monitors[0].position = Some(Position { x: 100, y: 0 });
+        app.selected_monitor = 1;
+
+        app.backend = Box::new(FakeBackend::new(vec![test_monitors()[0].clone(), projector.clone()]));
+        app.handle_key_event(KeyCode::Char('R').into());
+        assert_eq!(app.monitors.len(), 3);
+        assert_eq!(app.monitors[0].position, Some(Position { x: 100, y: 0 }));
+        assert!(app.monitors[1].disconnected);
+        assert_eq!(app.monitors[2], projector);
+        assert_eq!(app.status.current(), Some(&status::Message {
+            level: status::Level::Warning,
+            text: "Detected HDMI-A-1, Monitor 2 disconnected".to_string(),
+        }));
+        // the unplugged output is not selectable anymore
+        assert_eq!(app.selected_monitor, 0);
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_monitor, 2);
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_monitor, 0);
+        // the edit made before the hotplug can still be undone
+        assert_eq!(app.undo.depth(), (1, 0));
+        app.handle_key_event(KeyCode::Char('u').into());
+        assert_eq!(app.monitors.len(), 3);
+        assert_eq!(app.monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert!(app.monitors[1].disconnected);
+        assert!(!app.is_modified());
+
+        let now = Instant::now();
+
+        // the automatic refresh polls the backend and keeps quiet about its failures
+        app.backend = Box::new(FakeBackend::new(test_monitors()));
+        refresh::tick(&mut app, now);
+        assert!(app.monitors[1].disconnected);
+        refresh::tick(&mut app, now + refresh::REFRESH_INTERVAL);
+        assert!(!app.monitors[1].disconnected && app.monitors[2].disconnected);
+
+        let mut backend = FakeBackend::new(vec![]);
+        backend.error = Some("wlr-randr not found".to_string());
+        app.backend = Box::new(backend);
+        refresh::tick(&mut app, now + refresh::REFRESH_INTERVAL * 2);
+        assert!(app.error.is_none());
+        app.handle_key_event(KeyCode::Char('R').into());
+        assert!(matches!(app.error, Some(Error::Backend(_))));
+    }
 }
@@ -13,15 +13,21 @@ use ratatui::{
         Widget,
         canvas::{
             Canvas,
+            Line as CanvasLine,
             Rectangle,
         }
     },
 };
 use crate::App;
+use crate::layout;
 
 use crate::monitor::{
+    Alignment,
+    Axis,
     Monitor
     ,MonitorCanvas
+    ,Placement
+    ,SnapGuide
 };
 use crate::utils::TUIMode;
 
@@ -30,6 +36,9 @@ pub struct Map<'a>{
     pub mode: TUIMode,
     pub selected: usize,
     pub monitors:&'a Vec<Monitor>,
+    pub guide: Option<SnapGuide>,
+    pub alignment: Alignment,
+    pub reference: Option<usize>,
 }
 
 impl<'a> Widget for Map<'a>{
@@ -39,12 +48,19 @@ impl<'a> Widget for Map<'a>{
         let monitor_canvas = Monitor::get_monitors_canvas(self.monitors,&area);
 
         let title = Line::from(" Map ".white().bold());
+        let invalid = layout::invalid_monitors(self.monitors);
 
-        let block = Block::bordered()
+        let mut block = Block::bordered()
             .title(title.centered())
             .border_set(border::THICK)
             .border_style(Style::default().fg(
                 if self.mode == TUIMode::Move {Color::Yellow} else {Color::White}));
+        if self.mode == TUIMode::Move {
+            let reference = self.reference.map_or("-", |index| self.monitors[index].name.as_str());
+            block = block.title_bottom(
+                Line::from(format!(" Next to: {} Align: {} ", reference, self.alignment.label()).yellow()).left_aligned()
+            );
+        }
 
 
         Canvas::default()
@@ -55,15 +71,26 @@ impl<'a> Widget for Map<'a>{
             .paint(|ctx| {
                 let mut index = 0;
                 for monitor in self.monitors {
-                    if self.selected != index && monitor.enabled {
-                        self.render_enabled_monitor(ctx,&monitor_canvas, monitor, Color::Blue);
+                    if self.selected != index && monitor.enabled && !monitor.disconnected {
+                        let color = if invalid.contains(&index) {
+                            Color::Red
+                        } else if monitor.mirror_of.is_some() {
+                            Color::Magenta
+                        } else {
+                            Color::Blue
+                        };
+                        self.render_enabled_monitor(ctx,&monitor_canvas, monitor, color);
                     }
                     index += 1;
                 }
+                if let Some(guide) = self.guide {
+                    ctx.draw(&Map::guide_line(&monitor_canvas, guide));
+                }
                 index = 0;
                 for monitor in self.monitors {
-                    if self.selected == index && monitor.enabled {
-                            self.render_enabled_monitor(ctx,&monitor_canvas,monitor, Color::Yellow);
+                    if self.selected == index && monitor.enabled && !monitor.disconnected {
+                            let color = if invalid.contains(&index) {Color::LightRed} else {Color::Yellow};
+                            self.render_enabled_monitor(ctx,&monitor_canvas,monitor, color);
                     }
                     index += 1;
                 }
@@ -85,19 +112,79 @@ impl<'a> Map<'a> {
             KeyCode::Char('J') => Map::move_vertical(app,100),
             KeyCode::Char('H') => Map::move_horizontal(app,-100),
             KeyCode::Char('L') => Map::move_horizontal(app,100),
+            KeyCode::Left => Map::place(app,Placement::LeftOf),
+            KeyCode::Right => Map::place(app,Placement::RightOf),
+            KeyCode::Up => Map::place(app,Placement::Above),
+            KeyCode::Down => Map::place(app,Placement::Below),
+            KeyCode::Tab => Map::next_reference(app),
+            KeyCode::BackTab => app.alignment = app.alignment.next(),
+            KeyCode::Char('s') => Map::toggle_snapping(app),
             KeyCode::Esc => Map::change_mode(app,TUIMode::View),
             _ => {}
         }
     }
     fn change_mode(app:&mut App,mode: TUIMode) {
+        app.snap_guide = None;
         app.mode = mode;
     }
+    fn toggle_snapping(app:&mut App) {
+        app.free_move = !app.free_move;
+        app.snap_guide = None;
+    }
+    // the chosen reference while it can still be used, the nearest monitor otherwise
+    pub fn reference(app:&App) -> Option<usize> {
+        app.reference_monitor
+            .filter(|reference| Monitor::references(&app.monitors, app.selected_monitor).contains(reference))
+            .or_else(|| Monitor::nearest(&app.monitors, app.selected_monitor))
+    }
+    fn next_reference(app:&mut App) {
+        let references = Monitor::references(&app.monitors, app.selected_monitor);
+        let next = match Map::reference(app).and_then(|current| references.iter().position(|index| *index == current)) {
+            Some(position) => references.get(position + 1).or(references.first()),
+            None => references.first(),
+        };
+        app.reference_monitor = next.copied();
+    }
+    fn place(app:&mut App, placement: Placement) {
+        let Some(reference) = Map::reference(app) else {
+            app.status.warning("No monitor to place relative to");
+            return;
+        };
+        app.snap_guide = None;
+        if let Err(e) = Monitor::place_relative(&mut app.monitors, app.selected_monitor, reference, placement, app.alignment) {
+            app.status.error(e);
+        }
+    }
     fn move_vertical(app:&mut App, direction: i32) {
-        app.monitors[app.selected_monitor].move_vertical(direction);
+        Map::move_selected(app, Axis::Vertical, direction);
     }
 
     fn move_horizontal(app:&mut App, direction: i32) {
-        app.monitors[app.selected_monitor].move_horizontal(direction);
+        Map::move_selected(app, Axis::Horizontal, direction);
+    }
+
+    fn move_selected(app:&mut App, axis: Axis, distance: i32) {
+        if app.free_move {
+            let monitor = &mut app.monitors[app.selected_monitor];
+            match axis {
+                Axis::Horizontal => monitor.move_horizontal(distance),
+                Axis::Vertical => monitor.move_vertical(distance),
+            }
+            return;
+        }
+        app.snap_guide = Monitor::snap_move(&mut app.monitors, app.selected_monitor, axis, distance);
+    }
+
+    fn guide_line(monitor_canvas: &MonitorCanvas, guide: SnapGuide) -> CanvasLine {
+        let [left, right] = monitor_canvas.x_bounds;
+        let [bottom, top] = monitor_canvas.y_bounds;
+        match guide.axis {
+            Axis::Horizontal => CanvasLine::new(guide.position, bottom, guide.position, top, Color::Green),
+            Axis::Vertical => {
+                let y = (monitor_canvas.top - monitor_canvas.offset_y) as f64 - guide.position;
+                CanvasLine::new(left, y, right, y, Color::Green)
+            }
+        }
     }
 
     pub fn render_enabled_monitor(
@@ -107,26 +194,37 @@ impl<'a> Map<'a> {
         monitor: &Monitor,
         color: Color,
     ) {
-        let mut mode = monitor.get_current_resolution();
-        if mode.is_none() {
-            mode = monitor.get_prefered_resolution();
-        }
-        let width = mode.unwrap().width as f64 / monitor.scale.unwrap() as f64;
-        let x = monitor.position.clone().unwrap().x as f64;
-        let height = mode.unwrap().height as f64 / monitor.scale.unwrap() as f64;
-        let y = (monitor_canvas.top - monitor_canvas.offset_y - monitor.position.clone().unwrap().y) as f64 - height ; 
+        let Some((width, height)) = monitor.logical_size() else {
+            return;
+        };
+        // a mirrored output shows the content of its source, draw it on top of it
+        let source = monitor.mirror_of
+            .as_ref()
+            .and_then(|name| self.monitors.iter().find(|m| &m.name == name))
+            .unwrap_or(monitor);
+        let Some(position) = source.position.clone() else {
+            return;
+        };
+        let x = position.x as f64;
+        let y = (monitor_canvas.top - monitor_canvas.offset_y - position.y) as f64 - height ; 
 
         let x_margin = width * 0.07; 
         let y_margin = height * 0.07;
 
-        ctx.print(
-            x + x_margin, 
-            y + height - y_margin, 
-            Line::styled(
-                monitor.name.to_string(),
-                color
-            )
-        );
+        if monitor.mirror_of.is_none() {
+            let mut label = monitor.name.to_string();
+            for mirror in self.monitors.iter().filter(|m| m.enabled && m.mirror_of.as_ref() == Some(&monitor.name)) {
+                label.push_str(&format!(" ⧉ {}", mirror.name));
+            }
+            ctx.print(
+                x + x_margin, 
+                y + height - y_margin, 
+                Line::styled(
+                    label,
+                    color
+                )
+            );
+        }
 
         ctx.draw(&Rectangle {
             x,
@@ -150,6 +248,9 @@ mod tests {
             selected: 0,
             mode: TUIMode::View,
             monitors: &test_monitors(),
+            guide: None,
+            alignment: Alignment::Start,
+            reference: None,
         }; 
         let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
         
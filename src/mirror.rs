@@ -0,0 +1,227 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::monitor::Monitor;
+use crate::utils::TUIMode;
+use crate::App;
+
+#[derive(Debug)]
+pub struct Mirror<'a> {
+    pub state: TableState,
+    pub monitors: &'a Vec<Monitor>,
+    pub selected_monitor: usize,
+    pub error: Option<&'a str>,
+}
+
+impl<'a> Mirror<'a> {
+    pub fn new(
+        monitors: &'a Vec<Monitor>,
+        selected_monitor: usize,
+        error: Option<&'a str>,
+        selected: Option<usize>,
+    ) -> Self {
+        Mirror {
+            state: TableState::default()
+                .with_selected(selected),
+            monitors,
+            selected_monitor,
+            error,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('k')=> Mirror::previous(app),
+            KeyCode::Char('j')=> Mirror::next(app),
+            KeyCode::Char(' ')=> Mirror::select(app),
+            KeyCode::Esc => Mirror::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mirror_error = None;
+        app.mode = mode;
+    }
+
+    pub fn sources(monitors: &[Monitor], selected_monitor: usize) -> Vec<Option<usize>> {
+        let mut sources = vec![None];
+        sources.extend(
+            (0..monitors.len())
+                .filter(|index| *index != selected_monitor)
+                .map(Some)
+        );
+        sources
+    }
+
+    fn next(app:&mut App) {
+        let count = Mirror::sources(&app.monitors, app.selected_monitor).len();
+        app.selected_mirror = if app.selected_mirror >= count - 1 {
+            0
+        } else {
+            app.selected_mirror + 1
+        }
+    }
+
+    fn previous(app:&mut App) {
+        let count = Mirror::sources(&app.monitors, app.selected_monitor).len();
+        app.selected_mirror = if app.selected_mirror == 0 {
+            count - 1
+        } else {
+            app.selected_mirror - 1
+        }
+    }
+
+    fn select(app:&mut App) {
+        let sources = Mirror::sources(&app.monitors, app.selected_monitor);
+        let Some(source) = sources.get(app.selected_mirror) else {
+            return;
+        };
+        app.mirror_error = None;
+        match source {
+            None => app.monitors[app.selected_monitor].mirror_of = None,
+            Some(index) => {
+                let name = app.monitors[*index].name.clone();
+                match Monitor::validate_mirror(&app.monitors, app.selected_monitor, &name) {
+                    Ok(()) => app.monitors[app.selected_monitor].mirror_of = Some(name),
+                    Err(e) => app.mirror_error = Some(e),
+                }
+            }
+        }
+    }
+
+    fn sources_to_rows(&self) -> Vec<Row<'static>> {
+        let mirror_of = self.monitors[self.selected_monitor].mirror_of.as_deref();
+        Mirror::sources(self.monitors, self.selected_monitor)
+            .into_iter()
+            .map(|source| {
+                let (name, current, valid) = match source {
+                    None => ("None".to_string(), mirror_of.is_none(), true),
+                    Some(index) => {
+                        let name = self.monitors[index].name.clone();
+                        let current = mirror_of == Some(name.as_str());
+                        let valid = Monitor::validate_mirror(self.monitors, self.selected_monitor, &name).is_ok();
+                        (name, current, valid)
+                    }
+                };
+                Row::new(vec![
+                    Cell::default().content(
+                        Line::from(
+                            if current {
+                                "".green().to_string()
+                            } else {
+                                "".to_string()
+                            }
+                        )
+                        .centered()
+                    )
+                    .style(Style::default().fg(Color::Green)),
+                    Cell::from(name),
+                ])
+                .style(
+                    if valid {Style::default()} else {Style::default().fg(Color::DarkGray)}
+                )
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Mirror ".bold());
+        let mut block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        if let Some(error) = self.error {
+            block = block.title_bottom(Line::from(format!(" {} ", error).red()));
+        }
+
+        let widths = [
+            Constraint::Percentage(20),
+            Constraint::Percentage(80),
+        ];
+
+        let table = Table::new(self.sources_to_rows(),widths)
+            .column_spacing(1)
+            .header(
+                Row::new(vec![
+                    Cell::from(""),
+                    Cell::from("source"),
+                ])
+                    .bottom_margin(1)
+                    .bold()
+                    .green()
+                    .reversed()
+            )
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol(" ")
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn render_mirror() {
+        let monitors = test_monitors();
+        let mut mirror = Mirror::new(&monitors, 1, Some("Monitor 2 is disabled"), None);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 7));
+
+        mirror.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━ Mirror ━━━━━━━━━━┓",
+            "┃       source               ┃",
+            "┃                            ┃",
+            "┃       None                 ┃",
+            "┃       Monitor 1            ┃",
+            "┃                            ┃",
+            "┗ Monitor 2 is disabled ━━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        let title_style = Style::new().bold().fg(Color::White);
+        let header_style = Style::new().green().bold().reversed();
+        let current_style = Style::new().fg(Color::Green);
+        let invalid_style = Style::new().fg(Color::DarkGray);
+        let error_style = Style::new().fg(Color::Red);
+
+        expected.set_style(Rect::new(0, 0, 11, 1), border_style);
+        expected.set_style(Rect::new(11, 0, 8, 1), title_style);
+        expected.set_style(Rect::new(19, 0, 11, 1), border_style);
+
+        for y in 1..6 {
+            expected.set_style(Rect::new(0, y, 1, 1), border_style);
+            expected.set_style(Rect::new(29, y, 1, 1), border_style);
+        }
+        expected.set_style(Rect::new(1, 1, 28, 1), header_style);
+        expected.set_style(Rect::new(1, 3, 6, 1), current_style);
+        expected.set_style(Rect::new(1, 4, 28, 1), invalid_style);
+        expected.set_style(Rect::new(1, 4, 6, 1), current_style);
+
+        expected.set_style(Rect::new(0, 6, 1, 1), border_style);
+        expected.set_style(Rect::new(1, 6, 23, 1), error_style);
+        expected.set_style(Rect::new(24, 6, 6, 1), border_style);
+
+        assert_eq!(buf, expected);
+    }
+}
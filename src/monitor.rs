@@ -1,8 +1,8 @@
-use serde::Deserialize;
-use std::process::Command;
-use std::io::Write;
+use serde::{Deserialize, Deserializer, Serialize};
 use ratatui::layout::Rect;
-#[derive(Debug,Default, Clone, Deserialize)]
+use crate::error::{self, Error};
+use crate::layout::{self, LogicalRect};
+#[derive(Debug,Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Monitor {
     pub name: String,
     pub description: Option<String>,
@@ -13,15 +13,38 @@ pub struct Monitor {
     pub modes: Vec<Resolution>,
     pub position: Option<Position>,
     pub scale: Option<f32>,
+    #[serde(default, deserialize_with = "deserialize_transform")]
+    pub transform: u8,
+    #[serde(default, alias = "adaptive_sync")]
+    pub vrr: bool,
+    #[serde(default)]
+    pub mirror_of: Option<String>,
+    #[serde(default)]
+    pub active_workspace: Option<Workspace>,
+    #[serde(default)]
+    pub focused: bool,
+    #[serde(default)]
+    pub dpms_status: Option<bool>,
+    #[serde(default)]
+    pub extra_options: Vec<(String, String)>,
+    // unplugged while the TUI is open, kept in the list but no longer applied or written
+    #[serde(skip)]
+    pub disconnected: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position{
     pub x: i32,
     pub y: i32,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Resolution {
     pub width: i32,
     pub height: i32,
@@ -30,6 +53,110 @@ pub struct Resolution {
     pub current: bool,
 }
 
+pub const TRANSFORM_NAMES: [&str; 8] = [
+    "normal",
+    "90",
+    "180",
+    "270",
+    "flipped",
+    "flipped-90",
+    "flipped-180",
+    "flipped-270",
+];
+
+// transforms read from outside the model wrap around instead of panicking
+pub fn transform_name(transform: u8) -> &'static str {
+    TRANSFORM_NAMES[transform as usize % TRANSFORM_NAMES.len()]
+}
+
+pub fn deserialize_transform<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawTransform {
+        Index(u8),
+        Name(String),
+    }
+    match RawTransform::deserialize(deserializer)? {
+        RawTransform::Index(index) if (index as usize) < TRANSFORM_NAMES.len() => Ok(index),
+        RawTransform::Name(name) => TRANSFORM_NAMES
+            .iter()
+            .position(|transform| *transform == name)
+            .map(|index| index as u8)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown transform {}", name))),
+        RawTransform::Index(index) => Err(serde::de::Error::custom(format!("unknown transform {}", index))),
+    }
+}
+
+pub const SNAP_THRESHOLD: f64 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapGuide {
+    pub axis: Axis,
+    pub position: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    LeftOf,
+    RightOf,
+    Above,
+    Below,
+}
+
+impl Placement {
+    pub fn parse(value: &str) -> Option<Placement> {
+        match value {
+            "left-of" => Some(Placement::LeftOf),
+            "right-of" => Some(Placement::RightOf),
+            "above" => Some(Placement::Above),
+            "below" => Some(Placement::Below),
+            _ => None,
+        }
+    }
+}
+
+// Start is top for left-of/right-of and left for above/below
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Alignment {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+impl Alignment {
+    pub fn parse(value: &str) -> Option<Alignment> {
+        match value {
+            "top" | "left" | "start" => Some(Alignment::Start),
+            "center" | "centre" => Some(Alignment::Center),
+            "bottom" | "right" | "end" => Some(Alignment::End),
+            _ => None,
+        }
+    }
+
+    pub fn next(self) -> Alignment {
+        match self {
+            Alignment::Start => Alignment::Center,
+            Alignment::Center => Alignment::End,
+            Alignment::End => Alignment::Start,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Alignment::Start => "top/left",
+            Alignment::Center => "center",
+            Alignment::End => "bottom/right",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MonitorCanvas{
     pub top: i32,
@@ -40,22 +167,11 @@ pub struct MonitorCanvas{
 
 
 impl Monitor {
+    pub fn connected(monitors: &[Monitor]) -> Vec<Monitor> {
+        monitors.iter().filter(|monitor| !monitor.disconnected).cloned().collect()
+    }
 
-    pub fn get_monitors() -> Vec<Monitor> {
-        let output = Command::new("wlr-randr")
-            .arg("--json")
-            .output().expect("Failed to execute wlr-randr command");
-        let stdout = String::from_utf8(output.stdout).expect("Failed to convert output to string");
-        let new_monitors: Vec<Monitor> = match serde_json::from_str(&stdout) {
-            Ok(monitors) => monitors,
-            Err(e) => {
-                eprintln!("Deserialization error: {}", e);
-                Vec::new()
-            }
-        };
 
-        new_monitors
-    }
     pub fn get_monitors_canvas(monitors: &Vec<Monitor>, area: &Rect) -> MonitorCanvas {
         let mut left = 10000.0;
         let mut bottom = 10000.0;
@@ -63,19 +179,18 @@ impl Monitor {
         let mut top = -10000.0;
 
         for monitor in monitors {
-            if !monitor.enabled {
+            if !monitor.enabled || monitor.disconnected || monitor.mirror_of.is_some() {
                 continue;
             }
-            let mut mode = monitor.get_current_resolution();
-            if mode.is_none() {
-                mode = monitor.get_prefered_resolution();
-            }
+            let (Some((width, height)), Some(position)) = (monitor.logical_size(), &monitor.position) else {
+                continue;
+            };
 
-            let monitor_left = monitor.position.clone().unwrap().x as f64;
-            let monitor_right = monitor_left  + (mode.unwrap().width as f64 / monitor.scale.unwrap() as f64);
+            let monitor_left = position.x as f64;
+            let monitor_right = monitor_left + width;
 
-            let monitor_bottom = monitor.position.clone().unwrap().y as f64;
-            let monitor_top = monitor_bottom + (mode.unwrap().height as f64 / monitor.scale.unwrap() as f64);
+            let monitor_bottom = position.y as f64;
+            let monitor_top = monitor_bottom + height;
             
             if monitor_right > right {
                 right= monitor_right;
@@ -92,6 +207,11 @@ impl Monitor {
         }
 
 
+        // nothing placed, the empty canvas is centered on the origin
+        if left > right {
+            (left, bottom, right, top) = (0.0, 0.0, 0.0, 0.0);
+        }
+
         let margin = 50.0;
         left -= margin;
         bottom -= margin;
@@ -126,6 +246,43 @@ impl Monitor {
 
     }
 
+    pub fn logical_size(&self) -> Option<(f64, f64)> {
+        let mode = self.get_current_resolution().or(self.get_prefered_resolution())?;
+        let scale = self.scale.unwrap_or(1.0) as f64;
+        let width = mode.width as f64 / scale;
+        let height = mode.height as f64 / scale;
+        if self.is_rotated() {
+            Some((height, width))
+        } else {
+            Some((width, height))
+        }
+    }
+
+    pub fn is_rotated(&self) -> bool {
+        self.transform % 2 == 1
+    }
+
+    pub fn transform_name(&self) -> &'static str {
+        transform_name(self.transform)
+    }
+
+    pub fn identity(&self) -> String {
+        [&self.make, &self.model, &self.serial]
+            .iter()
+            .filter_map(|part| part.as_deref())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    // the identity, or the connector name for outputs without make, model and serial
+    pub fn key(&self) -> String {
+        match self.identity() {
+            identity if identity.is_empty() => self.name.clone(),
+            identity => identity,
+        }
+    }
+
     pub fn get_current_resolution(&self) -> Option<&Resolution> {
         self.modes
             .iter()
@@ -138,56 +295,251 @@ impl Monitor {
             .find(|m| m.preferred)
     }
     
-    pub fn set_current_resolution(&mut self, index: usize) {
-        if index < self.modes.len() {
-            for mode in &mut self.modes {
-                mode.current = false;
-            }
-            self.modes[index].current = true;
-        } else {
-            eprintln!("Index out of bounds: {}", index);
+    pub fn set_current_resolution(&mut self, index: usize) -> bool {
+        if index >= self.modes.len() {
+            return false;
+        }
+        for mode in &mut self.modes {
+            mode.current = false;
         }
+        self.modes[index].current = true;
+        true
     }
 
-    pub fn to_hyprland_config(&self) -> String {
-        let mode = match self.get_current_resolution() {
-            Some(m) => m,
-            None => {
-                self.get_prefered_resolution().expect("No preferred resolution found")
-            }
-        };
+    pub fn to_hyprland_config(&self) -> error::Result<String> {
+        Ok(format!("monitor = {}", self.to_hyprland_rule()?))
+    }
+
+    pub fn hyprland_target(&self) -> String {
+        match self.identity() {
+            identity if identity.is_empty() => self.name.clone(),
+            identity => format!("desc:{}", identity),
+        }
+    }
+
+    pub fn to_hyprland_rule(&self) -> error::Result<String> {
         if self.enabled {
-            format!(
-                "monitor = desc:{} {} {}, {}x{}@{}, {}x{}, {}",
-                self.make.as_deref().unwrap_or(""),
-                self.model.as_deref().unwrap_or(""),
-                self.serial.as_deref().unwrap_or(""),
+            let mode = self.get_current_resolution()
+                .or(self.get_prefered_resolution())
+                .ok_or_else(|| Error::MissingMode(self.name.clone()))?;
+            let position = self.position
+                .as_ref()
+                .ok_or_else(|| Error::MissingPosition(self.name.clone()))?;
+            let mut rule = format!(
+                "{}, {}x{}@{}, {}x{}, {}",
+                self.hyprland_target(),
                 mode.width, mode.height, mode.refresh,
-                self.position.clone().unwrap().x, self.position.clone().unwrap().y,
+                position.x, position.y,
                 self.scale.unwrap_or(1.0)
-            )
+            );
+            if self.transform != 0 {
+                rule.push_str(&format!(", transform, {}", self.transform));
+            }
+            if let Some(mirror_of) = &self.mirror_of {
+                rule.push_str(&format!(", mirror, {}", mirror_of));
+            }
+            if self.vrr && !self.extra_options.iter().any(|(key, _)| key == "vrr") {
+                rule.push_str(", vrr, 1");
+            }
+            for (key, value) in &self.extra_options {
+                rule.push_str(&format!(", {}", key));
+                if !value.is_empty() {
+                    rule.push_str(&format!(", {}", value));
+                }
+            }
+            Ok(rule)
         } else {
-            format!(
-                "monitor = {}, disabled",
+            Ok(format!(
+                "{}, disabled",
                 self.name
-            )
+            ))
         }
         
     }
-    pub fn save_hyprland_config(path:&String,monitors: &Vec<Monitor>) -> std::io::Result<()> {
-        let expanded_path = shellexpand::tilde(path).to_string();
-        let mut file = std::fs::OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(expanded_path)?;
-        for monitor in monitors {
-            let config_line = monitor.to_hyprland_config();
-            writeln!(file, "{}", config_line)?;
+    pub fn validate_mirror(monitors: &[Monitor], index: usize, source: &str) -> Result<(), String> {
+        let monitor = &monitors[index];
+        if monitor.name == source {
+            return Err(format!("{} cannot mirror itself", monitor.name));
+        }
+        let Some(source_monitor) = monitors.iter().find(|m| m.name == source) else {
+            return Err(format!("Unknown output {}", source));
+        };
+        if !monitor.enabled {
+            return Err(format!("{} is disabled", monitor.name));
+        }
+        if !source_monitor.enabled {
+            return Err(format!("Cannot mirror disabled output {}", source));
+        }
+        if let Some(mirror_of) = &source_monitor.mirror_of {
+            return Err(format!("{} already mirrors {}", source, mirror_of));
+        }
+        if let Some(mirrored_by) = monitors.iter().find(|m| m.mirror_of.as_deref() == Some(monitor.name.as_str())) {
+            return Err(format!("{} is mirrored by {}", monitor.name, mirrored_by.name));
         }
         Ok(())
     }
 
+    pub fn snap_targets(monitors: &[Monitor], index: usize, axis: Axis) -> Vec<(f64, f64)> {
+        let Some(rect) = LogicalRect::of(&monitors[index]) else {
+            return vec![];
+        };
+        let (width, height) = (rect.width, rect.height);
+        let size = match axis {
+            Axis::Horizontal => width,
+            Axis::Vertical => height,
+        };
+        let mut targets = vec![];
+        for (other_index, other) in monitors.iter().enumerate() {
+            if other_index == index || !other.enabled || other.disconnected || other.mirror_of.is_some() {
+                continue;
+            }
+            let (Some((other_width, other_height)), Some(position)) = (other.logical_size(), &other.position) else {
+                continue;
+            };
+            // only the outputs overlapping or touching on the other axis attract
+            let Some(other_rect) = LogicalRect::of(other) else {
+                continue;
+            };
+            let perpendicular = match axis {
+                Axis::Horizontal => rect.vertical_overlap(&other_rect),
+                Axis::Vertical => rect.horizontal_overlap(&other_rect),
+            };
+            if perpendicular < -layout::EPSILON {
+                continue;
+            }
+            let (start, other_size) = match axis {
+                Axis::Horizontal => (position.x as f64, other_width),
+                Axis::Vertical => (position.y as f64, other_height),
+            };
+            let end = start + other_size;
+            let center = start + other_size / 2.0;
+            // (position of the moved monitor, guide line) pairs: flush, edge aligned and centered
+            targets.push((end, end));
+            targets.push((start - size, start));
+            targets.push((start, start));
+            targets.push((end - size, end));
+            targets.push((center - size / 2.0, center));
+        }
+        targets
+    }
+
+    pub fn snap_move(monitors: &mut [Monitor], index: usize, axis: Axis, distance: i32) -> Option<SnapGuide> {
+        let position = monitors[index].position.clone()?;
+        let old = match axis {
+            Axis::Horizontal => position.x,
+            Axis::Vertical => position.y,
+        } as f64;
+        let new = old + distance as f64;
+        let direction = distance.signum() as f64;
+        // snap to the first target ahead of the old position, reached by the move or within the threshold after it
+        // (a target rounding to the old position would not move the output at all)
+        let target = Monitor::snap_targets(monitors, index, axis)
+            .into_iter()
+            .filter(|(target, _)| (target.round() - old) * direction > 0.0)
+            .filter(|(target, _)| (target - new) * direction <= SNAP_THRESHOLD)
+            .min_by(|(a, _), (b, _)| ((a - old) * direction).total_cmp(&((b - old) * direction)));
+
+        let monitor = &mut monitors[index];
+        let (moved, guide) = match target {
+            Some((target, guide)) => (target.round() as i32 - old as i32, Some(SnapGuide { axis, position: guide })),
+            None => (distance, None),
+        };
+        match axis {
+            Axis::Horizontal => monitor.move_horizontal(moved),
+            Axis::Vertical => monitor.move_vertical(moved),
+        }
+        guide
+    }
+
+    pub fn place_relative(
+        monitors: &mut [Monitor],
+        index: usize,
+        reference: usize,
+        placement: Placement,
+        alignment: Alignment,
+    ) -> Result<(), String> {
+        if index == reference {
+            return Err(format!("{} cannot be placed relative to itself", monitors[index].name));
+        }
+        let other = &monitors[reference];
+        let (Some((other_width, other_height)), Some(origin)) = (other.logical_size(), other.position.clone()) else {
+            return Err(format!("{} has no position", other.name));
+        };
+        let Some((width, height)) = monitors[index].logical_size() else {
+            return Err(format!("{} has no mode", monitors[index].name));
+        };
+        let align = |start: i32, other_size: f64, size: f64| match alignment {
+            Alignment::Start => start,
+            Alignment::Center => start + ((other_size - size) / 2.0).round() as i32,
+            Alignment::End => start + (other_size - size).round() as i32,
+        };
+        let position = match placement {
+            Placement::LeftOf => Position { x: origin.x - width.round() as i32, y: align(origin.y, other_height, height) },
+            Placement::RightOf => Position { x: origin.x + other_width.round() as i32, y: align(origin.y, other_height, height) },
+            Placement::Above => Position { x: align(origin.x, other_width, width), y: origin.y - height.round() as i32 },
+            Placement::Below => Position { x: align(origin.x, other_width, width), y: origin.y + other_height.round() as i32 },
+        };
+        monitors[index].position = Some(position);
+        Ok(())
+    }
+
+    // the enabled, connected, non mirrored monitors another one can be placed relative to
+    pub fn references(monitors: &[Monitor], index: usize) -> Vec<usize> {
+        monitors
+            .iter()
+            .enumerate()
+            .filter(|(other_index, other)| {
+                *other_index != index && other.enabled && !other.disconnected && other.mirror_of.is_none()
+            })
+            .map(|(other_index, _)| other_index)
+            .collect()
+    }
+
+    // the closest of the references, used by default for relative placement
+    pub fn nearest(monitors: &[Monitor], index: usize) -> Option<usize> {
+        let center = |monitor: &Monitor| {
+            let (width, height) = monitor.logical_size()?;
+            let position = monitor.position.as_ref()?;
+            Some((position.x as f64 + width / 2.0, position.y as f64 + height / 2.0))
+        };
+        let (x, y) = center(&monitors[index])?;
+        Monitor::references(monitors, index)
+            .into_iter()
+            .filter_map(|other_index| center(&monitors[other_index]).map(|(ox, oy)| (other_index, (ox - x).hypot(oy - y))))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(other_index, _)| other_index)
+    }
+
+    // human readable list of the settings changed since before
+    pub fn changes(&self, before: &Monitor) -> Vec<String> {
+        let mode = |monitor: &Monitor| match monitor.get_current_resolution().or(monitor.get_prefered_resolution()) {
+            Some(mode) => format!("{}x{}@{}", mode.width, mode.height, mode.refresh),
+            None => "N/A".to_string(),
+        };
+        let position = |monitor: &Monitor| match &monitor.position {
+            Some(position) => format!("{},{}", position.x, position.y),
+            None => "N/A".to_string(),
+        };
+        let mirror = |monitor: &Monitor| monitor.mirror_of.clone().unwrap_or_else(|| "none".to_string());
+        let mut changes = vec![];
+        if self.enabled != before.enabled {
+            changes.push(if self.enabled { "enabled".to_string() } else { "disabled".to_string() });
+        }
+        let fields = [
+            ("mode", mode(before), mode(self)),
+            ("position", position(before), position(self)),
+            ("scale", before.scale.unwrap_or(1.0).to_string(), self.scale.unwrap_or(1.0).to_string()),
+            ("transform", before.transform_name().to_string(), self.transform_name().to_string()),
+            ("mirror", mirror(before), mirror(self)),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                changes.push(format!("{} {} → {}", field, old, new));
+            }
+        }
+        changes
+    }
+
     pub fn move_vertical(&mut self, direction: i32) {
         if let Some(ref mut pos) = self.position { pos.y += direction};
     }
@@ -196,3 +548,156 @@ impl Monitor {
         if let Some(ref mut pos) = self.position { pos.x += direction};
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    fn side_by_side() -> Vec<Monitor> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.0);
+        monitors[1].position = Some(Position { x: 1900, y: 30 });
+        monitors
+    }
+
+    #[test]
+    fn snap_flush_to_edge() {
+        let mut monitors = side_by_side();
+        let guide = Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1920, y: 30 }));
+        assert_eq!(guide, Some(SnapGuide { axis: Axis::Horizontal, position: 1920.0 }));
+
+        // once flush, moving away is not pulled back
+        let guide = Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1930, y: 30 }));
+        assert_eq!(guide, None);
+    }
+
+    #[test]
+    fn snap_when_crossing_an_edge() {
+        let mut monitors = side_by_side();
+        monitors[1].position = Some(Position { x: 1850, y: 30 });
+        Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 100);
+        assert_eq!(monitors[1].position, Some(Position { x: 1920, y: 30 }));
+
+        let mut monitors = side_by_side();
+        monitors[1].position = Some(Position { x: 1750, y: 30 });
+        Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1760, y: 30 }));
+    }
+
+    #[test]
+    fn snap_fractional_edges_and_far_outputs() {
+        // 1920 / 1.75 = 1097.14, the edge rounds to the current position and must not hold the output
+        let mut monitors = side_by_side();
+        monitors[0].scale = Some(1.75);
+        monitors[1].position = Some(Position { x: 1097, y: 30 });
+        Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1107, y: 30 }));
+
+        // the edges of an output far away on the other axis do not attract
+        let mut monitors = side_by_side();
+        monitors[1].position = Some(Position { x: 1900, y: 2000 });
+        assert_eq!(Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10), None);
+        assert_eq!(monitors[1].position, Some(Position { x: 1910, y: 2000 }));
+    }
+
+    #[test]
+    fn snap_aligned_edges_and_centers() {
+        let mut monitors = side_by_side();
+        let guide = Monitor::snap_move(&mut monitors, 1, Axis::Vertical, -10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1900, y: 0 }));
+        assert_eq!(guide, Some(SnapGuide { axis: Axis::Vertical, position: 0.0 }));
+
+        // 1280x720 centered on 1920x1080 starts at y = 180
+        monitors[1].position = Some(Position { x: 1920, y: 140 });
+        let guide = Monitor::snap_move(&mut monitors, 1, Axis::Vertical, 10);
+        assert_eq!(monitors[1].position, Some(Position { x: 1920, y: 180 }));
+        assert_eq!(guide, Some(SnapGuide { axis: Axis::Vertical, position: 540.0 }));
+    }
+
+    #[test]
+    fn place_relative_to_other_monitor() {
+        let mut monitors = side_by_side();
+        Monitor::place_relative(&mut monitors, 1, 0, Placement::RightOf, Alignment::Start).unwrap();
+        assert_eq!(monitors[1].position, Some(Position { x: 1920, y: 0 }));
+
+        Monitor::place_relative(&mut monitors, 1, 0, Placement::LeftOf, Alignment::End).unwrap();
+        assert_eq!(monitors[1].position, Some(Position { x: -1280, y: 360 }));
+
+        // scaled and rotated sizes are taken into account
+        monitors[1].scale = Some(1.25);
+        monitors[1].transform = 1;
+        Monitor::place_relative(&mut monitors, 1, 0, Placement::Above, Alignment::Center).unwrap();
+        assert_eq!(monitors[1].position, Some(Position { x: 672, y: -1024 }));
+
+        Monitor::place_relative(&mut monitors, 0, 1, Placement::Below, Alignment::Start).unwrap();
+        assert_eq!(monitors[0].position, Some(Position { x: 672, y: 0 }));
+
+        assert!(Monitor::place_relative(&mut monitors, 1, 1, Placement::Below, Alignment::Start).is_err());
+        assert_eq!(Monitor::nearest(&monitors, 1), Some(0));
+    }
+
+    #[test]
+    fn describe_changes() {
+        let before = test_monitors();
+        let mut after = test_monitors();
+        assert!(after[0].changes(&before[0]).is_empty());
+
+        after[0].move_horizontal(10);
+        after[0].scale = Some(1.5);
+        after[0].set_current_resolution(1);
+        after[1].enabled = true;
+        after[1].transform = 1;
+        assert_eq!(
+            after[0].changes(&before[0]),
+            vec!["mode 1920x1080@60 → 1280x720@60", "position 0,0 → 10,0", "scale 1 → 1.5"]
+        );
+        assert_eq!(after[1].changes(&before[1]), vec!["enabled", "transform normal → 90"]);
+
+        // an out of range transform does not panic
+        after[1].transform = 12;
+        assert_eq!(after[1].changes(&before[1]), vec!["enabled", "transform normal → flipped"]);
+    }
+
+    #[test]
+    fn hyprland_rule_errors() {
+        let mut monitors = test_monitors();
+        monitors[0].modes.clear();
+        assert!(matches!(monitors[0].to_hyprland_rule(), Err(Error::MissingMode(name)) if name == "Monitor 1"));
+        monitors[1].enabled = true;
+        monitors[1].position = None;
+        assert!(matches!(monitors[1].to_hyprland_config(), Err(Error::MissingPosition(name)) if name == "Monitor 2"));
+        // a disabled output needs neither
+        monitors[1].enabled = false;
+        assert_eq!(monitors[1].to_hyprland_config().unwrap(), "monitor = Monitor 2, disabled");
+
+        // the canvas skips outputs it cannot place
+        monitors[0].enabled = false;
+        monitors[1].enabled = true;
+        let canvas = Monitor::get_monitors_canvas(&monitors, &Rect::new(0, 0, 100, 30));
+        assert!(canvas.x_bounds[0] < 0.0 && canvas.x_bounds[1] > 0.0);
+        assert!(canvas.y_bounds[0] < 0.0 && canvas.y_bounds[1] > 0.0);
+        assert_eq!(canvas.offset_y, 50);
+    }
+
+    #[test]
+    fn snap_ignores_disabled_and_mirrored() {
+        let mut monitors = side_by_side();
+        monitors[0].enabled = false;
+        assert_eq!(Monitor::snap_move(&mut monitors, 1, Axis::Horizontal, 10), None);
+        assert_eq!(monitors[1].position, Some(Position { x: 1910, y: 30 }));
+
+        monitors[0].enabled = true;
+        monitors[0].mirror_of = Some("Monitor 2".to_string());
+        assert!(Monitor::snap_targets(&monitors, 1, Axis::Horizontal).is_empty());
+
+        // an unplugged output is neither a snap target nor a placement reference
+        monitors[0].mirror_of = None;
+        monitors[0].disconnected = true;
+        assert!(Monitor::snap_targets(&monitors, 1, Axis::Horizontal).is_empty());
+        assert_eq!(Monitor::nearest(&monitors, 1), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::{self, Error};
+use crate::monitor::Monitor;
+
+pub fn output_node(monitor: &Monitor) -> error::Result<String> {
+    let mut node = format!("output \"{}\" {{\n", monitor.key());
+    if !monitor.enabled {
+        node.push_str("    off\n}\n");
+        return Ok(node);
+    }
+    let mode = monitor.get_current_resolution()
+        .or(monitor.get_prefered_resolution())
+        .ok_or_else(|| Error::MissingMode(monitor.name.clone()))?;
+    let position = monitor.position
+        .as_ref()
+        .ok_or_else(|| Error::MissingPosition(monitor.name.clone()))?;
+    node.push_str(&format!("    mode \"{}x{}@{:.3}\"\n", mode.width, mode.height, mode.refresh));
+    node.push_str(&format!("    scale {}\n", monitor.scale.unwrap_or(1.0)));
+    node.push_str(&format!("    transform \"{}\"\n", monitor.transform_name()));
+    node.push_str(&format!("    position x={} y={}\n", position.x, position.y));
+    if monitor.vrr {
+        node.push_str("    variable-refresh-rate\n");
+    }
+    node.push_str("}\n");
+    Ok(node)
+}
+
+fn node_target(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("output")?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let (target, rest) = rest.split_once('"')?;
+    rest.trim_start().starts_with('{').then_some(target)
+}
+
+fn depth(line: &str) -> i32 {
+    line.matches('{').count() as i32 - line.matches('}').count() as i32
+}
+
+// the output nodes of the connected outputs are replaced, the rest of config.kdl is kept
+pub fn render(content: &str, monitors: &[Monitor]) -> error::Result<String> {
+    let mut written = vec![false; monitors.len()];
+    let mut output = String::new();
+    let mut skipping = 0;
+    for line in content.lines() {
+        if skipping > 0 {
+            skipping += depth(line);
+            continue;
+        }
+        let index = node_target(line)
+            .and_then(|target| monitors.iter().position(|monitor| monitor.name == target || monitor.identity() == target));
+        match index {
+            Some(index) => {
+                skipping = depth(line);
+                if !written[index] {
+                    written[index] = true;
+                    output.push_str(&output_node(&monitors[index])?);
+                }
+            },
+            None => {
+                output.push_str(line);
+                output.push('\n');
+            },
+        }
+    }
+    for (monitor, written) in monitors.iter().zip(written) {
+        if !written {
+            output.push_str(&output_node(monitor)?);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    const CONFIG: &str = "input {
+    keyboard {
+        xkb { layout \"us\"; }
+    }
+}
+
+output \"Monitor 1\" {
+    mode \"1280x720@60.000\"
+    scale 2
+}
+output \"eDP-1\" {
+    off
+}
+";
+
+    #[test]
+    fn replace_output_nodes() {
+        let mut monitors = test_monitors();
+        monitors[0].vrr = true;
+        let rendered = render(CONFIG, &monitors).unwrap();
+        assert_eq!(
+            rendered,
+            "input {
+    keyboard {
+        xkb { layout \"us\"; }
+    }
+}
+
+output \"Some Description 1\" {
+    mode \"1920x1080@60.000\"
+    scale 1
+    transform \"normal\"
+    position x=0 y=0
+    variable-refresh-rate
+}
+output \"eDP-1\" {
+    off
+}
+output \"Some Description 2\" {
+    off
+}
+"
+        );
+        assert_eq!(render(&rendered, &monitors).unwrap(), rendered);
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Paragraph,Widget},
+};
+use crate::layout;
+use crate::monitor::Monitor;
+
+pub const MAX_LINES: usize = 3;
+
+// the layout problems, one per line above the status line
+#[derive(Debug)]
+pub struct Problems {
+    pub messages: Vec<String>,
+}
+
+impl Problems {
+    pub fn new(monitors: &[Monitor]) -> Self {
+        Problems {
+            messages: layout::validate(monitors)
+                .iter()
+                .map(|issue| issue.message(monitors))
+                .collect(),
+        }
+    }
+
+    pub fn height(&self) -> u16 {
+        self.messages.len().min(MAX_LINES) as u16
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        // the problems that do not fit are counted on the last line
+        let shown = if self.messages.len() > MAX_LINES { MAX_LINES - 1 } else { self.messages.len() };
+        let mut lines: Vec<Line> = self.messages[..shown]
+            .iter()
+            .map(|message| Line::from(format!(" ⚠ {}", message).red().bold()))
+            .collect();
+        if shown < self.messages.len() {
+            lines.push(Line::from(format!(" ⚠ {} more layout problems", self.messages.len() - shown).red().bold()));
+        }
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color,Style};
+
+    #[test]
+    fn render_problems() {
+        let problems = Problems {
+            messages: (1..=4).map(|i| format!("problem {}", i)).collect(),
+        };
+        assert_eq!(problems.height(), 3);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 3));
+
+        problems.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            " ⚠ problem 1                  ",
+            " ⚠ problem 2                  ",
+            " ⚠ 2 more layout problems     ",
+        ]);
+        let style = Style::new().fg(Color::Red).bold();
+        expected.set_style(Rect::new(0, 0, 12, 1), style);
+        expected.set_style(Rect::new(0, 1, 12, 1), style);
+        expected.set_style(Rect::new(0, 2, 25, 1), style);
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::monitor::{Monitor, Position};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mode {
+    pub width: i32,
+    pub height: i32,
+    pub refresh: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorSettings {
+    pub output: String,
+    pub enabled: bool,
+    pub mode: Option<Mode>,
+    pub position: Option<Position>,
+    pub scale: Option<f32>,
+    #[serde(default)]
+    pub transform: u8,
+    #[serde(default)]
+    pub mirror_of: Option<String>,
+}
+
+impl MonitorSettings {
+    // the mirrored output is stored by identity too
+    pub fn from_monitor(monitor: &Monitor, monitors: &[Monitor]) -> Self {
+        MonitorSettings {
+            output: monitor.key(),
+            enabled: monitor.enabled,
+            mode: monitor
+                .get_current_resolution()
+                .or(monitor.get_prefered_resolution())
+                .map(|mode| Mode {
+                    width: mode.width,
+                    height: mode.height,
+                    refresh: mode.refresh,
+                }),
+            position: monitor.position.clone(),
+            scale: monitor.scale,
+            transform: monitor.transform,
+            mirror_of: monitor.mirror_of.as_ref().map(|name| {
+                monitors
+                    .iter()
+                    .find(|other| &other.name == name)
+                    .map_or_else(|| name.clone(), Monitor::key)
+            }),
+        }
+    }
+
+    pub fn matches(&self, monitor: &Monitor) -> bool {
+        self.output == monitor.key()
+    }
+
+    // outputs are the key and connector name of the connected monitors
+    pub fn apply_to(&self, monitor: &mut Monitor, outputs: &[(String, String)]) {
+        monitor.enabled = self.enabled;
+        if self.position.is_some() {
+            monitor.position = self.position.clone();
+        }
+        if self.scale.is_some() {
+            monitor.scale = self.scale;
+        }
+        monitor.transform = self.transform;
+        // profiles saved with connector names are kept as they are
+        monitor.mirror_of = self.mirror_of.as_ref().map(|key| {
+            outputs
+                .iter()
+                .find(|(output, _)| output == key)
+                .map_or_else(|| key.clone(), |(_, name)| name.clone())
+        });
+        if let Some(mode) = &self.mode
+            && let Some(index) = monitor.modes.iter().position(|m| {
+                m.width == mode.width && m.height == mode.height && (m.refresh - mode.refresh).abs() < 0.05
+            }) {
+            monitor.set_current_resolution(index);
+        }
+    }
+}
+
+pub fn from_monitors(monitors: &[Monitor]) -> Vec<MonitorSettings> {
+    monitors.iter().map(|monitor| MonitorSettings::from_monitor(monitor, monitors)).collect()
+}
+
+pub fn matches(profile: &[MonitorSettings], monitors: &[Monitor]) -> bool {
+    profile
+        .iter()
+        .all(|settings| monitors.iter().any(|monitor| settings.matches(monitor)))
+}
+
+pub fn outputs(monitors: &[Monitor]) -> Vec<(String, String)> {
+    monitors.iter().map(|monitor| (monitor.key(), monitor.name.clone())).collect()
+}
+
+pub fn load(profile: &[MonitorSettings], monitors: &mut [Monitor]) -> usize {
+    let outputs = outputs(monitors);
+    let mut loaded = 0;
+    for monitor in monitors.iter_mut() {
+        if let Some(settings) = profile.iter().find(|settings| settings.matches(monitor)) {
+            settings.apply_to(monitor, &outputs);
+            loaded += 1;
+        }
+    }
+    loaded
+}
+
+pub fn best_match<'a>(
+    profiles: &'a BTreeMap<String, Vec<MonitorSettings>>,
+    monitors: &[Monitor],
+) -> Option<(&'a String, &'a Vec<MonitorSettings>)> {
+    let connected = monitors.len();
+    profiles
+        .iter()
+        .filter(|(_, settings)| !settings.is_empty() && matches(settings, monitors))
+        // an exact match of the connected outputs wins, then the profile covering the most outputs
+        .max_by_key(|(_, settings)| (settings.len() == connected, settings.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn store_mirror_by_identity() {
+        let mut monitors = test_monitors();
+        monitors[1].mirror_of = Some("Monitor 1".to_string());
+        let profile = from_monitors(&monitors);
+        assert_eq!(profile[1].mirror_of.as_deref(), Some(monitors[0].key().as_str()));
+
+        // the same display on another connector
+        let mut docked = test_monitors();
+        docked[0].name = "DP-3".to_string();
+        assert_eq!(load(&profile, &mut docked), 2);
+        assert_eq!(docked[1].mirror_of.as_deref(), Some("DP-3"));
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::BTreeMap;
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::error::{Error, ErrorPopup};
+use crate::monitor::Monitor;
+use crate::profile::{self, MonitorSettings};
+use crate::utils::TUIMode;
+use crate::App;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileInputKind {
+    New,
+    Rename,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileInput {
+    pub kind: ProfileInputKind,
+    pub name: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Profiles<'a> {
+    pub state: TableState,
+    pub profiles: &'a BTreeMap<String, Vec<MonitorSettings>>,
+    pub monitors: &'a Vec<Monitor>,
+    pub input: Option<&'a ProfileInput>,
+}
+
+impl<'a> Profiles<'a> {
+    pub fn new(
+        profiles: &'a BTreeMap<String, Vec<MonitorSettings>>,
+        monitors: &'a Vec<Monitor>,
+        input: Option<&'a ProfileInput>,
+        selected: Option<usize>,
+    ) -> Self {
+        Profiles {
+            state: TableState::default()
+                .with_selected(selected),
+            profiles,
+            monitors,
+            input,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        if app.profile_input.is_some() {
+            Profiles::handle_input_events(app,key_event);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Char('k')=> Profiles::previous(app),
+            KeyCode::Char('j')=> Profiles::next(app),
+            KeyCode::Char(' ') | KeyCode::Enter => Profiles::load(app),
+            KeyCode::Char('n')=> Profiles::start_input(app,ProfileInputKind::New),
+            KeyCode::Char('r')=> Profiles::start_input(app,ProfileInputKind::Rename),
+            KeyCode::Char('d')=> Profiles::start_input(app,ProfileInputKind::Delete),
+            KeyCode::Esc => Profiles::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+
+    fn handle_input_events(app:&mut App, key_event: KeyEvent) {
+        let Some(input) = app.profile_input.as_mut() else {
+            return;
+        };
+        if input.kind == ProfileInputKind::Delete {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Enter => Profiles::delete(app),
+                KeyCode::Char('n') | KeyCode::Esc => app.profile_input = None,
+                _ => {}
+            }
+            return;
+        }
+        match key_event.code {
+            KeyCode::Char(c) => {
+                input.name.push(c);
+                input.error = None;
+            },
+            KeyCode::Backspace => {
+                input.name.pop();
+                input.error = None;
+            },
+            KeyCode::Enter => Profiles::submit_input(app),
+            KeyCode::Esc => app.profile_input = None,
+            _ => {}
+        }
+    }
+
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    fn selected_name(app:&App) -> Option<String> {
+        app.config.profiles.keys().nth(app.selected_profile).cloned()
+    }
+
+    fn select_name(app:&mut App, name: &str) {
+        app.selected_profile = app.config.profiles
+            .keys()
+            .position(|key| key == name)
+            .unwrap_or(0);
+    }
+
+    fn next(app:&mut App) {
+        if app.config.profiles.is_empty() {
+            return;
+        }
+        app.selected_profile = if app.selected_profile >= app.config.profiles.len() - 1 {
+            0
+        } else {
+            app.selected_profile + 1
+        }
+    }
+
+    fn previous(app:&mut App) {
+        if app.config.profiles.is_empty() {
+            return;
+        }
+        app.selected_profile = if app.selected_profile == 0 {
+            app.config.profiles.len() - 1
+        } else {
+            app.selected_profile - 1
+        }
+    }
+
+    fn load(app:&mut App) {
+        if let Some(name) = Profiles::selected_name(app) {
+            profile::load(&app.config.profiles[&name], &mut app.monitors);
+            app.status.info(format!("Loaded profile {}", name));
+        }
+    }
+
+    fn start_input(app:&mut App, kind: ProfileInputKind) {
+        let name = match kind {
+            ProfileInputKind::New => String::new(),
+            ProfileInputKind::Rename | ProfileInputKind::Delete => match Profiles::selected_name(app) {
+                Some(name) => name,
+                None => return,
+            },
+        };
+        app.profile_input = Some(ProfileInput { kind, name, error: None });
+    }
+
+    fn submit_input(app:&mut App) {
+        let Some(input) = app.profile_input.take() else {
+            return;
+        };
+        let name = input.name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        // another saved profile is never replaced, the input stays open with the error
+        let renamed_onto_itself = input.kind == ProfileInputKind::Rename
+            && Profiles::selected_name(app).as_deref() == Some(name.as_str());
+        if app.config.profiles.contains_key(&name) && !renamed_onto_itself {
+            app.profile_input = Some(ProfileInput {
+                error: Some(format!("{} already exists", name)),
+                ..input
+            });
+            return;
+        }
+        let previous = (app.config.profiles.clone(), app.selected_profile);
+        match input.kind {
+            ProfileInputKind::New => {
+                // an unplugged output would keep the profile from ever matching again
+                app.config.profiles.insert(name.clone(), profile::from_monitors(&Monitor::connected(&app.monitors)));
+            },
+            ProfileInputKind::Rename => {
+                let Some(old_name) = Profiles::selected_name(app) else {
+                    return;
+                };
+                if let Some(settings) = app.config.profiles.remove(&old_name) {
+                    app.config.profiles.insert(name.clone(), settings);
+                }
+            },
+            ProfileInputKind::Delete => return,
+        }
+        Profiles::select_name(app, &name);
+        if Profiles::save(app, previous) {
+            app.status.info(format!("Saved profile {}", name));
+        }
+    }
+
+    fn delete(app:&mut App) {
+        app.profile_input = None;
+        if let Some(name) = Profiles::selected_name(app) {
+            let previous = (app.config.profiles.clone(), app.selected_profile);
+            app.config.profiles.remove(&name);
+            if app.selected_profile >= app.config.profiles.len() {
+                app.selected_profile = app.config.profiles.len().saturating_sub(1);
+            }
+            if Profiles::save(app, previous) {
+                app.status.info(format!("Deleted profile {}", name));
+            }
+        }
+    }
+
+    // a profile change that did not reach the disk is rolled back
+    fn save(app:&mut App, previous: (BTreeMap<String, Vec<MonitorSettings>>, usize)) -> bool {
+        let saved = app.config.save().map_err(Error::from);
+        if ErrorPopup::report(app, saved).is_some() {
+            return true;
+        }
+        (app.config.profiles, app.selected_profile) = previous;
+        false
+    }
+
+    fn profiles_to_rows(&self) -> Vec<Row<'static>> {
+        self.profiles
+            .iter()
+            .map(|(name, settings)| {
+                let connected = profile::matches(settings, self.monitors);
+                Row::new(vec![
+                    Cell::default().content(
+                        Line::from(
+                            if connected {
+                                "".green().to_string()
+                            } else {
+                                "".red().to_string()
+                            }
+                        )
+                        .centered()
+                    )
+                    .style(
+                        Style::default().fg(
+                            if connected {Color::Green} else {Color::Red}
+                        )
+                    ),
+                    Cell::from(name.clone()),
+                    Cell::default().content(
+                        Line::from(settings.len().to_string())
+                            .centered()
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Profiles ".bold());
+        let mut block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        if let Some(input) = self.input {
+            let mut spans = match input.kind {
+                ProfileInputKind::New => vec![" New profile: ".white(), format!("{}_ ", input.name).blue().bold()],
+                ProfileInputKind::Rename => vec![" Rename: ".white(), format!("{}_ ", input.name).blue().bold()],
+                ProfileInputKind::Delete => vec![
+                    format!(" Delete {} ? ", input.name).white(),
+                    "<y> ".blue().bold(),
+                    "<n> ".blue().bold(),
+                ],
+            };
+            if let Some(error) = &input.error {
+                spans.push(format!("{} ", error).red());
+            }
+            block = block.title_bottom(Line::from(spans));
+        }
+
+        let widths = [
+            Constraint::Percentage(15),
+            Constraint::Percentage(60),
+            Constraint::Percentage(25),
+        ];
+
+        let table = Table::new(self.profiles_to_rows(),widths)
+            .column_spacing(1)
+            .header(
+                Row::new(vec![
+                    Cell::from(""),
+                    Cell::from("name"),
+                    Cell::from(
+                        Line::from("outputs")
+                            .centered()
+                    ),
+                ])
+                    .bottom_margin(1)
+                    .bold()
+                    .green()
+                    .reversed()
+            )
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol(" ")
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn render_profiles() {
+        let monitors = test_monitors();
+        let mut unplugged = profile::from_monitors(&monitors);
+        unplugged[1].output = "Other Monitor 3".to_string();
+        let profiles = BTreeMap::from([
+            ("desk".to_string(), profile::from_monitors(&monitors)),
+            ("office".to_string(), unplugged),
+        ]);
+        let input = ProfileInput {
+            kind: ProfileInputKind::New,
+            name: "home".to_string(),
+            error: None,
+        };
+        let mut widget = Profiles::new(&profiles, &monitors, Some(&input), None);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 7));
+
+        widget.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━ Profiles ━━━━━━━━━┓",
+            "┃     name            outputs┃",
+            "┃                            ┃",
+            "┃    desk               2   ┃",
+            "┃    office             2   ┃",
+            "┃                            ┃",
+            "┗ New profile: home_ ━━━━━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        let title_style = Style::new().bold().fg(Color::White);
+        let header_style = Style::new().green().bold().reversed();
+        let ok_style = Style::new().fg(Color::Green);
+        let nok_style = Style::new().fg(Color::Red);
+        let row_style = Style::new();
+        let input_label_style = Style::new().fg(Color::White);
+        let input_value_style = Style::new().blue().bold();
+
+        expected.set_style(Rect::new(0, 0, 10, 1), border_style);
+        expected.set_style(Rect::new(10, 0, 10, 1), title_style);
+        expected.set_style(Rect::new(20, 0, 10, 1), border_style);
+
+        for y in 1..6 {
+            expected.set_style(Rect::new(0, y, 1, 1), border_style);
+            expected.set_style(Rect::new(29, y, 1, 1), border_style);
+        }
+        expected.set_style(Rect::new(1, 1, 28, 1), header_style);
+        expected.set_style(Rect::new(1, 3, 4, 1), ok_style);
+        expected.set_style(Rect::new(5, 3, 24, 1), row_style);
+        expected.set_style(Rect::new(1, 4, 4, 1), nok_style);
+        expected.set_style(Rect::new(5, 4, 24, 1), row_style);
+
+        expected.set_style(Rect::new(0, 6, 1, 1), border_style);
+        expected.set_style(Rect::new(1, 6, 14, 1), input_label_style);
+        expected.set_style(Rect::new(15, 6, 6, 1), input_value_style);
+        expected.set_style(Rect::new(21, 6, 9, 1), border_style);
+
+        assert_eq!(buf, expected);
+    }
+}
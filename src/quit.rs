@@ -0,0 +1,148 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Clear,Paragraph,Widget},
+};
+use crate::monitor::Monitor;
+use crate::save::SaveConfirm;
+use crate::utils::TUIMode;
+use crate::App;
+
+#[derive(Debug)]
+pub struct QuitConfirm {
+    pub changes: Vec<String>,
+}
+
+impl QuitConfirm {
+    pub fn new(saved: &[Monitor], monitors: &[Monitor]) -> Self {
+        QuitConfirm {
+            changes: QuitConfirm::diff(saved, monitors),
+        }
+    }
+
+    // one line per changed monitor, matched by name as the list can be reordered
+    pub fn diff(saved: &[Monitor], monitors: &[Monitor]) -> Vec<String> {
+        monitors
+            .iter()
+            .filter_map(|monitor| {
+                let changes = match saved.iter().find(|before| before.name == monitor.name) {
+                    Some(before) => monitor.changes(before),
+                    None => vec!["added".to_string()],
+                };
+                (!changes.is_empty()).then(|| format!("{}: {}", monitor.name, changes.join(", ")))
+            })
+            .collect()
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            // saved through the save popup, for its layout problems and diff, then exits once written
+            KeyCode::Char('s') | KeyCode::Char('y') => {
+                app.mode = TUIMode::View;
+                SaveConfirm::request(app);
+                app.quit_after_save = app.mode == TUIMode::SaveConfirm;
+            },
+            KeyCode::Char('d') => app.exit = true,
+            KeyCode::Char('c') | KeyCode::Esc => app.mode = TUIMode::View,
+            _ => {}
+        }
+    }
+
+    pub fn request(app:&mut App) {
+        if app.is_modified() {
+            app.mode = TUIMode::QuitConfirm;
+        } else {
+            app.exit = true;
+        }
+    }
+
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.min(70);
+        let height = area.height.min(self.changes.len() as u16 + 6);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Quit ".white().bold());
+        let instructions = Line::from(vec![
+            " Save ".white(),
+            "<s> ".blue().bold(),
+            " Discard ".white(),
+            "<d> ".blue().bold(),
+            " Cancel ".white(),
+            "<c> ".blue().bold(),
+        ]);
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let mut text = vec![
+            Line::from(""),
+            Line::from("Unsaved changes :").centered(),
+        ];
+        for change in &self.changes {
+            text.push(Line::from(format!(" {}", change).yellow()).centered());
+        }
+        text.push(Line::from(""));
+
+        Clear.render(area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn render_quit_confirm() {
+        let saved = test_monitors();
+        let mut monitors = test_monitors();
+        monitors[0].move_vertical(-10);
+        monitors.swap(0, 1);
+        let quit = QuitConfirm::new(&saved, &monitors);
+        assert_eq!(quit.changes, vec!["Monitor 1: position 0,0 → 0,-10"]);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 6));
+        quit.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━━━━━━━ Quit ━━━━━━━━━━━━━━━━┓",
+            "┃                                      ┃",
+            "┃           Unsaved changes :          ┃",
+            "┃    Monitor 1: position 0,0 → 0,-10   ┃",
+            "┃                                      ┃",
+            "┗━ Save <s>  Discard <d>  Cancel <c> ━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        expected.set_style(Rect::new(0, 0, 40, 1), border_style);
+        expected.set_style(Rect::new(17, 0, 6, 1), Style::new().fg(Color::White).bold());
+        expected.set_style(Rect::new(0, 1, 1, 5), border_style);
+        expected.set_style(Rect::new(39, 1, 1, 5), border_style);
+        expected.set_style(Rect::new(4, 3, 32, 1), Style::new().fg(Color::Yellow));
+        expected.set_style(Rect::new(0, 5, 40, 1), border_style);
+        let mut x = 2;
+        for (label, key) in [(" Save ", "<s> "), (" Discard ", "<d> "), (" Cancel ", "<c> ")] {
+            expected.set_style(Rect::new(x, 5, label.len() as u16, 1), Style::new().fg(Color::White));
+            x += label.len() as u16;
+            expected.set_style(Rect::new(x, 5, key.len() as u16, 1), Style::new().blue().bold());
+            x += key.len() as u16;
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,132 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Clear,Paragraph,Widget,Wrap},
+};
+use crate::apply::ApplyConfirm;
+use crate::backend::WlrRandr;
+use crate::monitor::Monitor;
+use crate::utils::TUIMode;
+use crate::App;
+
+// applies the edited layout with wlr-randr whatever the backend, after showing the command
+#[derive(Debug)]
+pub struct RandrConfirm {
+    pub command: String,
+}
+
+impl RandrConfirm {
+    pub fn new(monitors: &[Monitor]) -> Self {
+        RandrConfirm {
+            command: WlrRandr::command_line(&Monitor::connected(monitors)),
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => RandrConfirm::run(app),
+            KeyCode::Char('n') | KeyCode::Esc => app.mode = TUIMode::View,
+            _ => {}
+        }
+    }
+
+    pub fn request(app:&mut App) {
+        app.mode = TUIMode::RandrConfirm;
+    }
+
+    fn run(app:&mut App) {
+        app.mode = TUIMode::View;
+        ApplyConfirm::start_with(app, |app| {
+            let args = WlrRandr::apply_args(&Monitor::connected(&app.monitors));
+            app.runner.run("wlr-randr", &args).map(|_| ())
+        });
+    }
+
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.min(70);
+        let lines = (self.command.chars().count() as u16).div_ceil(width.saturating_sub(2).max(1));
+        let height = area.height.min(lines + 5);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Apply with wlr-randr ".white().bold());
+        let instructions = Line::from(vec![
+            " Run ".white(),
+            "<y> ".blue().bold(),
+            " Cancel ".white(),
+            "<n> ".blue().bold(),
+        ]);
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let text = vec![
+            Line::from(""),
+            Line::from("Run this command ?").centered(),
+            Line::from(""),
+            Line::from(self.command.as_str().cyan()),
+        ];
+
+        Clear.render(area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn render_randr_confirm() {
+        let popup = RandrConfirm::new(&test_monitors());
+        let area = popup.popup_area(Rect::new(0, 0, 60, 20));
+        assert_eq!(area, Rect::new(0, 6, 60, 8));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 8));
+        popup.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━━━━━━━━━ Apply with wlr-randr ━━━━━━━━━━━━━━━━━━┓",
+            "┃                                                          ┃",
+            "┃                    Run this command ?                    ┃",
+            "┃                                                          ┃",
+            "┃wlr-randr --output 'Monitor 1' --on --mode 1920x1080@60Hz ┃",
+            "┃--pos 0,0 --scale 1 --transform normal --output 'Monitor  ┃",
+            "┃2' --off                                                  ┃",
+            "┗━━━━━━━━━━━━━━━━━━ Run <y>  Cancel <n> ━━━━━━━━━━━━━━━━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        expected.set_style(Rect::new(0, 0, 60, 1), border_style);
+        expected.set_style(Rect::new(19, 0, 22, 1), Style::new().fg(Color::White).bold());
+        expected.set_style(Rect::new(0, 1, 1, 6), border_style);
+        expected.set_style(Rect::new(59, 1, 1, 6), border_style);
+        expected.set_style(Rect::new(1, 4, 57, 1), Style::new().fg(Color::Cyan));
+        expected.set_style(Rect::new(1, 5, 56, 1), Style::new().fg(Color::Cyan));
+        expected.set_style(Rect::new(1, 6, 8, 1), Style::new().fg(Color::Cyan));
+        expected.set_style(Rect::new(0, 7, 60, 1), border_style);
+        let mut x = 19;
+        for (label, key) in [(" Run ", "<y> "), (" Cancel ", "<n> ")] {
+            expected.set_style(Rect::new(x, 7, label.len() as u16, 1), Style::new().fg(Color::White));
+            x += label.len() as u16;
+            expected.set_style(Rect::new(x, 7, key.len() as u16, 1), Style::new().blue().bold());
+            x += key.len() as u16;
+        }
+        assert_eq!(buf, expected);
+    }
+}
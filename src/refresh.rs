@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant};
+use crate::error::{Error, ErrorPopup};
+use crate::list::MonitorList;
+use crate::monitor::Monitor;
+use crate::App;
+
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Changes {
+    pub added: Vec<String>,
+    pub reconnected: Vec<String>,
+    pub disconnected: Vec<String>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.reconnected.is_empty() && self.disconnected.is_empty()
+    }
+}
+
+// the outputs still present keep their edits, new ones are appended and missing ones marked disconnected
+pub fn merge(monitors: &mut Vec<Monitor>, detected: &[Monitor]) -> Changes {
+    let mut changes = Changes::default();
+    // outputs are matched by display first, so a display keeps its edits on another port
+    let mut matches: Vec<Option<usize>> = monitors.iter()
+        .map(|monitor| detected.iter().position(|output| output.key() == monitor.key()))
+        .collect();
+    for (index, monitor) in monitors.iter().enumerate() {
+        if matches[index].is_none() {
+            matches[index] = detected.iter().enumerate()
+                .position(|(output_index, output)| output.name == monitor.name && !matches.contains(&Some(output_index)));
+        }
+    }
+    for (monitor, found) in monitors.iter_mut().zip(&matches) {
+        let Some(output) = found.map(|index| &detected[index]) else {
+            if !monitor.disconnected {
+                changes.disconnected.push(monitor.name.clone());
+            }
+            monitor.disconnected = true;
+            continue;
+        };
+        if monitor.disconnected {
+            changes.reconnected.push(output.name.clone());
+        } else if output.key() != monitor.key() {
+            // another display plugged into the same port
+            changes.added.push(output.name.clone());
+        }
+        monitor.disconnected = false;
+        refresh_hardware(monitor, output);
+    }
+    for (index, output) in detected.iter().enumerate() {
+        if !matches.contains(&Some(index)) {
+            changes.added.push(output.name.clone());
+            monitors.push(output.clone());
+        }
+    }
+    changes
+}
+
+// the detected output knows the display, the edited mode is kept when the display still supports it
+fn refresh_hardware(monitor: &mut Monitor, output: &Monitor) {
+    let current = monitor.get_current_resolution().cloned();
+    monitor.name = output.name.clone();
+    monitor.description = output.description.clone();
+    monitor.make = output.make.clone();
+    monitor.model = output.model.clone();
+    monitor.serial = output.serial.clone();
+    monitor.modes = output.modes.clone();
+    let Some(current) = current else {
+        return;
+    };
+    let supported = monitor.modes.iter().position(|mode| {
+        mode.width == current.width && mode.height == current.height && mode.refresh == current.refresh
+    });
+    if let Some(index) = supported {
+        monitor.set_current_resolution(index);
+    }
+}
+
+// errors of the automatic refresh are not reported, it is retried on the next tick
+pub fn run(app:&mut App, manual: bool) {
+    let detected = app.backend
+        .get_monitors()
+        .map_err(Error::Backend)
+        .and_then(|mut detected| {
+            app.config.merge_monitors_config(&mut detected)?;
+            Ok(detected)
+        });
+    let detected = match detected {
+        Ok(detected) => detected,
+        Err(e) => {
+            if manual {
+                ErrorPopup::report(app, Err::<(), Error>(e));
+            }
+            return;
+        }
+    };
+    let changes = merge(&mut app.monitors, &detected);
+    // hotplug is not an unsaved edit
+    merge(&mut app.saved_monitors, &detected);
+    if changes.is_empty() {
+        if manual {
+            app.status.info("No display change");
+        }
+        return;
+    }
+    if app.selected_monitor >= app.monitors.len() {
+        app.selected_monitor = app.monitors.len().saturating_sub(1);
+    }
+    // the unplugged output cannot stay selected
+    if app.monitors[app.selected_monitor].disconnected {
+        let (order, _) = MonitorList::selected_row(app);
+        if let Some(index) = order.into_iter().find(|index| !app.monitors[*index].disconnected) {
+            app.selected_monitor = index;
+        }
+    }
+    // one message for the whole hotplug, the status line only shows the newest
+    let text = changes.added.iter().map(|name| format!("Detected {}", name))
+        .chain(changes.reconnected.iter().map(|name| format!("{} reconnected", name)))
+        .chain(changes.disconnected.iter().map(|name| format!("{} disconnected", name)))
+        .collect::<Vec<String>>()
+        .join(", ");
+    match changes.disconnected.is_empty() {
+        true => app.status.info(text),
+        false => app.status.warning(text),
+    }
+}
+
+pub fn tick(app:&mut App, now: Instant) {
+    if app.error.is_some() || app.pending_apply.is_some() {
+        return;
+    }
+    match app.next_refresh {
+        Some(next) if now < next => {},
+        Some(_) => {
+            app.next_refresh = Some(now + REFRESH_INTERVAL);
+            run(app, false);
+        },
+        None => app.next_refresh = Some(now + REFRESH_INTERVAL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::Position;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn merge_detected_outputs() {
+        let mut monitors = test_monitors();
+        monitors[0].position = Some(Position { x: 100, y: 0 });
+        let mut projector = test_monitors()[1].clone();
+        projector.name = "HDMI-A-1".to_string();
+        projector.serial = Some("3".to_string());
+
+        let changes = merge(&mut monitors, &[test_monitors()[0].clone(), projector.clone()]);
+        assert_eq!(changes, Changes {
+            added: vec!["HDMI-A-1".to_string()],
+            reconnected: vec![],
+            disconnected: vec!["Monitor 2".to_string()],
+        });
+        assert_eq!(monitors.len(), 3);
+        assert_eq!(monitors[0].position, Some(Position { x: 100, y: 0 }));
+        assert!(!monitors[0].disconnected && monitors[1].disconnected);
+        assert_eq!(monitors[2], projector);
+        assert_eq!(Monitor::connected(&monitors).len(), 2);
+
+        let changes = merge(&mut monitors, &test_monitors());
+        assert_eq!(changes.reconnected, vec!["Monitor 2"]);
+        assert_eq!(changes.disconnected, vec!["HDMI-A-1"]);
+        assert!(merge(&mut monitors, &test_monitors()).is_empty());
+    }
+
+    #[test]
+    fn merge_refreshes_the_display() {
+        // the same display on another port keeps its edits
+        let mut monitors = test_monitors();
+        monitors[0].scale = Some(2.0);
+        let mut moved = test_monitors();
+        moved[0].name = "DP-3".to_string();
+        assert!(merge(&mut monitors, &moved).is_empty());
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].name, "DP-3");
+        assert_eq!(monitors[0].scale, Some(2.0));
+
+        // another display on the same port brings its own modes
+        let mut monitors = test_monitors();
+        monitors[0].set_current_resolution(1);
+        let mut swapped = test_monitors();
+        swapped[0].serial = Some("9".to_string());
+        swapped[0].description = Some("Description 9".to_string());
+        swapped[0].modes.truncate(1);
+        let changes = merge(&mut monitors, &swapped);
+        assert_eq!(changes.added, vec!["Monitor 1"]);
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].key(), swapped[0].key());
+        assert_eq!(monitors[0].description, swapped[0].description);
+        assert_eq!(monitors[0].modes, swapped[0].modes);
+        assert_eq!(monitors[0].get_current_resolution().unwrap().width, 1920);
+
+        // a mode the display still supports stays selected
+        let mut monitors = test_monitors();
+        monitors[0].set_current_resolution(1);
+        merge(&mut monitors, &test_monitors());
+        assert_eq!(monitors[0].get_current_resolution().unwrap().width, 1280);
+    }
+}
@@ -59,7 +59,9 @@ impl<'a> Resolutions<'a> {
     }
 
     fn select(app:&mut App) {
-        app.monitors[app.selected_monitor].set_current_resolution(app.selected_resolution);
+        if !app.monitors[app.selected_monitor].set_current_resolution(app.selected_resolution) {
+            app.status.warning(format!("No resolution at index {}", app.selected_resolution));
+        }
     }
 
     fn resolutions_to_rows(&self) -> Vec<Row<'static>> {
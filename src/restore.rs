@@ -0,0 +1,217 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint,Direction,Layout,Rect},
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Paragraph,StatefulWidget,Row,Table,Cell,TableState,Widget},
+};
+
+use crate::backup;
+use crate::error::{Error, ErrorPopup};
+use crate::utils::TUIMode;
+use crate::writer::Target;
+use crate::App;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub target: Target,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+#[derive(Debug)]
+pub struct RestorePicker<'a> {
+    pub state: TableState,
+    pub backups: &'a [Backup],
+}
+
+impl<'a> RestorePicker<'a> {
+    pub fn new(backups: &'a [Backup], selected: Option<usize>) -> Self {
+        RestorePicker {
+            state: TableState::default()
+                .with_selected(selected),
+            backups,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('k')=> RestorePicker::previous(app),
+            KeyCode::Char('j')=> RestorePicker::next(app),
+            KeyCode::Char(' ') | KeyCode::Enter => RestorePicker::restore(app),
+            KeyCode::Esc => RestorePicker::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    // list the backups of every written file with their content, newest first for each file
+    pub fn open(app:&mut App) {
+        let mut backups = vec![];
+        for target in app.config.targets() {
+            let listed = backup::list(&target.expanded_path()).map_err(Error::from);
+            let Some(listed) = ErrorPopup::report(app, listed) else {
+                return;
+            };
+            backups.extend(listed.into_iter().map(|path| Backup {
+                content: std::fs::read_to_string(&path).unwrap_or_default(),
+                target: target.clone(),
+                path,
+            }));
+        }
+        app.backups = backups;
+        app.selected_backup = 0;
+        app.mode = TUIMode::Backups;
+    }
+
+    fn next(app:&mut App) {
+        if app.backups.is_empty() {
+            return;
+        }
+        app.selected_backup = if app.selected_backup >= app.backups.len() - 1 {
+            0
+        } else {
+            app.selected_backup + 1
+        }
+    }
+
+    fn previous(app:&mut App) {
+        if app.backups.is_empty() {
+            return;
+        }
+        app.selected_backup = if app.selected_backup == 0 {
+            app.backups.len() - 1
+        } else {
+            app.selected_backup - 1
+        }
+    }
+
+    fn restore(app:&mut App) {
+        let Some(selected) = app.backups.get(app.selected_backup).cloned() else {
+            return;
+        };
+        let path = selected.target.expanded_path();
+        let label = backup::label(&path, &selected.path);
+        // only the monitors config is read back into the layout
+        let read_back = selected.target == app.config.monitors_target();
+        let restored = backup::restore(&path, &selected.path, app.config.backups)
+            .map_err(Error::from)
+            // the saved layout only takes what the restored file sets, other pending edits stay unsaved
+            .and_then(|_| match read_back {
+                true => app.config.merge_monitors_config(&mut app.monitors)
+                    .and_then(|_| app.config.merge_monitors_config(&mut app.saved_monitors)),
+                false => Ok(()),
+            });
+        if ErrorPopup::report(app, restored).is_none() {
+            return;
+        }
+        app.mode = TUIMode::View;
+        app.status.info(format!("Restored the backup of {} from {}", selected.target.path, label));
+    }
+
+    fn backups_to_rows(&self) -> Vec<Row<'static>> {
+        // the file is named when the backups come from several ones
+        let several = self.backups.iter().any(|backup| backup.target != self.backups[0].target);
+        self.backups
+            .iter()
+            .map(|backup| {
+                let mut label = backup::label(&backup.target.expanded_path(), &backup.path);
+                if several {
+                    label.push_str(&format!(" {}", backup.target.path));
+                }
+                Row::new(vec![
+                    Cell::from(label),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Percentage(40),
+                Constraint::Percentage(60),
+            ])
+            .split(area);
+
+        let block = Block::bordered()
+            .title(Line::from(" Backups ".bold()).white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+        let table = Table::new(self.backups_to_rows(), [Constraint::Percentage(100)])
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .highlight_symbol(" ")
+            .block(block);
+        StatefulWidget::render(
+            table,
+            layout[0],
+            buf,
+            &mut self.state,
+        );
+
+        let content = match self.state.selected().and_then(|selected| self.backups.get(selected)) {
+            Some(backup) => backup.content.clone(),
+            None => "No backup".to_string(),
+        };
+        Paragraph::new(content)
+            .block(
+                Block::bordered()
+                    .title(Line::from(" Content ".bold()).white().centered())
+                    .border_set(border::THICK)
+                    .border_style(Style::default().fg(Color::White))
+            )
+            .render(layout[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::ConfigFormat;
+
+    #[test]
+    fn render_backups() {
+        let target = Target { format: ConfigFormat::Hyprland, path: "/tmp/monitors.conf".to_string() };
+        let backup = |path: &str, content: &str| Backup {
+            target: target.clone(),
+            path: PathBuf::from(path),
+            content: content.to_string(),
+        };
+        let backups = vec![
+            backup("/tmp/monitors.conf.20261018-142501.123.bak", "monitor = DP-1, disabled\n"),
+            backup("/tmp/monitors.conf.20261017-080000.000.bak", ""),
+        ];
+        let mut widget = RestorePicker::new(&backups, Some(0));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 5));
+
+        widget.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━ Backups ━━━━━━━┓┏━━━━━━━━━━━━ Content ━━━━━━━━━━━━━┓",
+            "┃ 2026-10-18 14:25:01 ┃┃monitor = DP-1, disabled          ┃",
+            "┃  2026-10-17 08:00:00 ┃┃                                  ┃",
+            "┃                      ┃┃                                  ┃",
+            "┗━━━━━━━━━━━━━━━━━━━━━━┛┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        let content_border_style = Style::new().fg(Color::White);
+        let title_style = Style::new().bold().fg(Color::White);
+
+        expected.set_style(Rect::new(0, 0, 24, 5), border_style);
+        expected.set_style(Rect::new(7, 0, 9, 1), title_style);
+        expected.set_style(Rect::new(1, 2, 22, 2), Style::reset());
+        expected.set_style(Rect::new(24, 0, 36, 5), content_border_style);
+        expected.set_style(Rect::new(37, 0, 9, 1), title_style);
+        expected.set_style(Rect::new(25, 1, 34, 3), Style::reset());
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,187 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,Clear,Paragraph,Widget},
+};
+use crate::diff::DiffLine;
+use crate::error::ErrorPopup;
+use crate::layout;
+use crate::monitor::Monitor;
+use crate::utils::TUIMode;
+use crate::writer;
+use crate::App;
+
+#[derive(Debug)]
+pub struct SaveConfirm<'a> {
+    pub problems: Vec<String>,
+    pub diff: &'a [DiffLine],
+    pub scroll: u16,
+}
+
+impl<'a> SaveConfirm<'a> {
+    pub fn new(monitors: &[Monitor], diff: &'a [DiffLine], scroll: u16) -> Self {
+        SaveConfirm {
+            problems: layout::validate(monitors)
+                .iter()
+                .map(|issue| issue.message(monitors))
+                .collect(),
+            diff,
+            scroll,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.mode = TUIMode::View;
+                let problems = layout::validate(&app.monitors).len();
+                let written = app.write();
+                if ErrorPopup::report(app, written).is_some() {
+                    let message = format!("Saved {}", app.config.monitors_config_path);
+                    match problems {
+                        0 => app.status.info(message),
+                        n => app.status.warning(format!("{} with {} layout problem(s)", message, n)),
+                    }
+                    app.exit = app.quit_after_save;
+                }
+                app.quit_after_save = false;
+            },
+            KeyCode::Char('n') | KeyCode::Esc => {
+                app.mode = TUIMode::View;
+                app.quit_after_save = false;
+                app.status.info("Save cancelled");
+            },
+            KeyCode::Char('j') => {
+                let max = SaveConfirm::new(&app.monitors, &app.save_diff, 0).max_scroll(app.area);
+                app.save_scroll = app.save_scroll.saturating_add(1).min(max);
+            },
+            KeyCode::Char('k') => app.save_scroll = app.save_scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    // preview the changes of the config file before writing it
+    pub fn request(app:&mut App) {
+        let diff = writer::diff(&app.config, &app.monitors);
+        let Some(diff) = ErrorPopup::report(app, diff) else {
+            return;
+        };
+        app.save_diff = diff;
+        app.save_scroll = 0;
+        app.mode = TUIMode::SaveConfirm;
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut text = vec![Line::from("")];
+        if !self.problems.is_empty() {
+            text.push(Line::from("The layout has problems :").centered());
+            for problem in &self.problems {
+                text.push(Line::from(format!(" {}", problem).red()).centered());
+            }
+            text.push(Line::from(""));
+        }
+        if self.diff.is_empty() {
+            text.push(Line::from("No changes to the config file").centered());
+        }
+        for line in self.diff {
+            let content = format!(" {}", line.text());
+            text.push(match line {
+                DiffLine::File(_) => Line::from(content.bold()),
+                DiffLine::Hunk(_) => Line::from(content.cyan()),
+                DiffLine::Context(_) => Line::from(content),
+                DiffLine::Added(_) => Line::from(content.green()),
+                DiffLine::Removed(_) => Line::from(content.red()),
+            });
+        }
+        text.push(Line::from(""));
+        text
+    }
+
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.min(80);
+        let height = area.height.min(self.lines().len() as u16 + 2);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    // the last line stays at the bottom of the popup
+    pub fn max_scroll(&self, area: Rect) -> u16 {
+        let visible = self.popup_area(area).height.saturating_sub(2);
+        (self.lines().len() as u16).saturating_sub(visible)
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Save ".white().bold());
+        let instructions = Line::from(vec![
+            if self.problems.is_empty() {" Save "} else {" Save anyway "}.white(),
+            "<y> ".blue().bold(),
+            " Scroll ".white(),
+            "<k/j> ".blue().bold(),
+            " Cancel ".white(),
+            "<n> ".blue().bold(),
+        ]);
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        Clear.render(area, buf);
+        Paragraph::new(self.lines())
+            .block(block)
+            .scroll((self.scroll, 0))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn render_save_confirm() {
+        let diff = diff::unified("monitor = DP-1, disabled\n", "monitor = DP-1, preferred, auto, 1\n", diff::CONTEXT);
+        let save = SaveConfirm::new(&test_monitors(), &diff, 0);
+        assert_eq!(save.popup_area(Rect::new(0, 0, 46, 20)), Rect::new(0, 6, 46, 7));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 46, 7));
+        save.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━━━━━━━━━━━━━━━━━ Save ━━━━━━━━━━━━━━━━━━━┓",
+            "┃                                            ┃",
+            "┃ @@ -1,1 +1,1 @@                            ┃",
+            "┃ -monitor = DP-1, disabled                  ┃",
+            "┃ +monitor = DP-1, preferred, auto, 1        ┃",
+            "┃                                            ┃",
+            "┗━━━━ Save <y>  Scroll <k/j>  Cancel <n> ━━━━┛",
+        ]);
+        let border_style = Style::new().fg(Color::Yellow);
+        expected.set_style(Rect::new(0, 0, 46, 1), border_style);
+        expected.set_style(Rect::new(20, 0, 6, 1), Style::new().fg(Color::White).bold());
+        expected.set_style(Rect::new(0, 1, 1, 5), border_style);
+        expected.set_style(Rect::new(45, 1, 1, 5), border_style);
+        expected.set_style(Rect::new(1, 2, 16, 1), Style::new().fg(Color::Cyan));
+        expected.set_style(Rect::new(1, 3, 26, 1), Style::new().fg(Color::Red));
+        expected.set_style(Rect::new(1, 4, 36, 1), Style::new().fg(Color::Green));
+        expected.set_style(Rect::new(0, 6, 46, 1), border_style);
+        let mut x = 5;
+        for (label, key) in [(" Save ", "<y> "), (" Scroll ", "<k/j> "), (" Cancel ", "<n> ")] {
+            expected.set_style(Rect::new(x, 6, label.len() as u16, 1), Style::new().fg(Color::White));
+            x += label.len() as u16;
+            expected.set_style(Rect::new(x, 6, key.len() as u16, 1), Style::new().blue().bold());
+            x += key.len() as u16;
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
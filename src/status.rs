@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Stylize,Color},
+    text::Line,
+    widgets::Widget,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Level::Info => Duration::from_secs(3),
+            Level::Warning => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(8),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Level::Info => Color::Green,
+            Level::Warning => Color::Yellow,
+            Level::Error => Color::Red,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Level::Info => "",
+            Level::Warning => "",
+            Level::Error => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub level: Level,
+    pub text: String,
+}
+
+// the last message pushed by the event handlers, shown for the duration of its level
+#[derive(Debug, Default)]
+pub struct Status {
+    message: Option<Message>,
+    shown_since: Option<Instant>,
+}
+
+impl Status {
+    // feedback for the last key replaces the message on screen instead of waiting behind it
+    pub fn push(&mut self, level: Level, text: impl Into<String>) {
+        self.message = Some(Message { level, text: text.into() });
+        self.shown_since = Some(Instant::now());
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Level::Info, text);
+    }
+
+    pub fn warning(&mut self, text: impl Into<String>) {
+        self.push(Level::Warning, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Level::Error, text);
+    }
+
+    pub fn current(&self) -> Option<&Message> {
+        self.message.as_ref()
+    }
+
+    pub fn tick(&mut self, now: Instant) {
+        let (Some(message), Some(shown_since)) = (&self.message, self.shown_since) else {
+            return;
+        };
+        if now.saturating_duration_since(shown_since) >= message.level.duration() {
+            self.message = None;
+            self.shown_since = None;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StatusBar<'a> {
+    pub message: Option<&'a Message>,
+    // the undo and redo depths
+    pub history: (usize, usize),
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(status: &'a Status, history: (usize, usize)) -> Self {
+        StatusBar {
+            message: status.current(),
+            history,
+        }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        if self.history != (0, 0) {
+            let (undo, redo) = self.history;
+            Line::from(vec![
+                " Undo ".white(),
+                "<u> ".blue().bold(),
+                format!("{} ", undo).white(),
+                " Redo ".white(),
+                "<Ctrl-r> ".blue().bold(),
+                format!("{} ", redo).white(),
+            ]).right_aligned().render(area, buf);
+        }
+        let Some(message) = self.message else {
+            return;
+        };
+        let color = message.level.color();
+        Line::from(vec![
+            format!(" {} ", message.level.symbol()).fg(color).bold(),
+            message.text.clone().fg(color),
+        ]).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn show_the_newest_message() {
+        let mut status = Status::default();
+        assert!(status.current().is_none());
+        status.tick(Instant::now());
+
+        status.info("Saved");
+        status.error("Failed to apply");
+        assert_eq!(status.current().unwrap().text, "Failed to apply");
+
+        let start = Instant::now();
+        status.tick(start);
+        assert_eq!(status.current().unwrap().level, Level::Error);
+        status.tick(start + Level::Warning.duration());
+        assert_eq!(status.current().unwrap().level, Level::Error);
+        status.tick(start + Level::Error.duration());
+        assert!(status.current().is_none());
+
+        // a new message replaces the one on screen
+        status.warning("Nothing to undo");
+        status.info("Undone");
+        status.tick(Instant::now());
+        assert_eq!(status.current(), Some(&Message { level: Level::Info, text: "Undone".to_string() }));
+    }
+
+    #[test]
+    fn render_status_bar() {
+        let mut status = Status::default();
+        status.info("Saved");
+        status.warning("Nothing to undo");
+        let mut buf = Buffer::empty(Rect::new(0, 0, 50, 1));
+        StatusBar::new(&status, (2, 0)).render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "  Nothing to undo    Undo <u> 2  Redo <Ctrl-r> 0 ",
+        ]);
+        expected.set_style(Rect::new(0, 0, 3, 1), Style::new().fg(Color::Yellow).bold());
+        expected.set_style(Rect::new(3, 0, 15, 1), Style::new().fg(Color::Yellow));
+        expected.set_style(Rect::new(21, 0, 6, 1), Style::new().fg(Color::White));
+        expected.set_style(Rect::new(27, 0, 4, 1), Style::new().blue().bold());
+        expected.set_style(Rect::new(31, 0, 8, 1), Style::new().fg(Color::White));
+        expected.set_style(Rect::new(39, 0, 9, 1), Style::new().blue().bold());
+        expected.set_style(Rect::new(48, 0, 2, 1), Style::new().fg(Color::White));
+
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::backend::Sway;
+use crate::error::{self, Error};
+use crate::hyprland::find_mode;
+use crate::monitor::{Monitor, Position, TRANSFORM_NAMES};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputRule {
+    pub target: String,
+    pub settings: Vec<String>,
+}
+
+// splits an output line into words, a quoted identifier is one word
+fn words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            },
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub fn parse_rule(line: &str) -> Option<OutputRule> {
+    let line = line.split('#').next()?.trim();
+    let mut words = words(line).into_iter();
+    if words.next()? != "output" {
+        return None;
+    }
+    let target = words.next()?;
+    let settings: Vec<String> = words.collect();
+    // blocks and bare output lines are left as they are
+    if settings.is_empty() || settings.iter().any(|word| word == "{") {
+        return None;
+    }
+    Some(OutputRule { target, settings })
+}
+
+impl OutputRule {
+    pub fn matches(&self, monitor: &Monitor) -> bool {
+        self.target == monitor.name || self.target == monitor.identity()
+    }
+
+    pub fn apply_to(&self, monitor: &mut Monitor) {
+        let mut words = self.settings.iter().map(String::as_str);
+        while let Some(word) = words.next() {
+            match word {
+                "enable" => monitor.enabled = true,
+                "disable" => monitor.enabled = false,
+                "mode" | "resolution" | "res" => {
+                    let mode = match words.next() {
+                        Some("--custom") => words.next(),
+                        mode => mode,
+                    };
+                    if let Some(index) = mode.and_then(|mode| find_mode(monitor, mode)) {
+                        monitor.set_current_resolution(index);
+                    }
+                },
+                "pos" | "position" => {
+                    if let (Some(Ok(x)), Some(Ok(y))) = (words.next().map(str::parse), words.next().map(str::parse)) {
+                        monitor.position = Some(Position { x, y });
+                    }
+                },
+                "scale" => {
+                    if let Some(Ok(scale)) = words.next().map(str::parse::<f32>) {
+                        monitor.scale = Some(scale);
+                    }
+                },
+                "transform" => {
+                    if let Some(index) = words.next().and_then(|name| TRANSFORM_NAMES.iter().position(|t| *t == name)) {
+                        monitor.transform = index as u8;
+                    }
+                },
+                "adaptive_sync" => monitor.vrr = words.next() == Some("on"),
+                _ => {},
+            }
+        }
+    }
+}
+
+pub fn output_line(monitor: &Monitor) -> error::Result<String> {
+    if monitor.enabled {
+        monitor.get_current_resolution()
+            .or(monitor.get_prefered_resolution())
+            .ok_or_else(|| Error::MissingMode(monitor.name.clone()))?;
+        monitor.position
+            .as_ref()
+            .ok_or_else(|| Error::MissingPosition(monitor.name.clone()))?;
+    }
+    let target = match monitor.identity() {
+        identity if identity.is_empty() => monitor.name.clone(),
+        identity => format!("\"{}\"", identity),
+    };
+    Ok(format!("output {} {}", target, Sway::output_settings(monitor)))
+}
+
+pub fn merge(content: &str, monitors: &mut [Monitor]) {
+    for rule in content.lines().filter_map(parse_rule) {
+        if let Some(monitor) = monitors.iter_mut().find(|monitor| rule.matches(monitor)) {
+            rule.apply_to(monitor);
+        }
+    }
+}
+
+pub fn render(content: &str, monitors: &[Monitor]) -> error::Result<String> {
+    let mut written = vec![false; monitors.len()];
+    let mut output = String::new();
+    for line in content.lines() {
+        match parse_rule(line).and_then(|rule| monitors.iter().position(|monitor| rule.matches(monitor))) {
+            Some(index) if !written[index] => {
+                written[index] = true;
+                output.push_str(&output_line(&monitors[index])?);
+                output.push('\n');
+            },
+            // a second rule for an output we already wrote would override it, it is kept commented out
+            Some(_) => {
+                output.push_str(&format!("# {}\n", line));
+            },
+            None => {
+                output.push_str(line);
+                output.push('\n');
+            },
+        }
+    }
+    for (monitor, written) in monitors.iter().zip(written) {
+        if !written {
+            output.push_str(&output_line(monitor)?);
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    const CONFIG: &str = "# outputs
+output \"Some Description 1\" mode 1280x720@60Hz pos 100 200 scale 1.5 transform 90 adaptive_sync on
+output HDMI-A-1 disable
+output * bg ~/wallpaper.png fill
+output \"Some Description 2\" {
+    mode 1920x1080
+}
+";
+
+    #[test]
+    fn merge_and_render_outputs_config() {
+        let mut monitors = test_monitors();
+        merge(CONFIG, &mut monitors);
+        assert_eq!(monitors[0].get_current_resolution().unwrap().width, 1280);
+        assert_eq!(monitors[0].position, Some(Position { x: 100, y: 200 }));
+        assert_eq!(monitors[0].scale, Some(1.5));
+        assert_eq!(monitors[0].transform, 1);
+        assert!(monitors[0].vrr);
+        assert_eq!(monitors[1], test_monitors()[1]);
+
+        monitors[0].vrr = false;
+        assert_eq!(
+            render(CONFIG, &monitors).unwrap(),
+            "# outputs
+output \"Some Description 1\" enable mode 1280x720@60.000Hz pos 100 200 scale 1.5 transform 90 adaptive_sync off
+output HDMI-A-1 disable
+output * bg ~/wallpaper.png fill
+output \"Some Description 2\" {
+    mode 1920x1080
+}
+output \"Some Description 2\" disable
+"
+        );
+
+        monitors[0].position = None;
+        assert!(matches!(render("", &monitors), Err(Error::MissingPosition(_))));
+    }
+
+    #[test]
+    fn comment_out_duplicate_outputs() {
+        let content = "output \"Some Description 1\" scale 2\noutput \"Some Description 1\" scale 1.5\n";
+        let mut monitors = test_monitors();
+        merge(content, &mut monitors);
+        let rendered = render(content, &monitors).unwrap();
+        assert_eq!(
+            rendered,
+            "output \"Some Description 1\" enable mode 1920x1080@60.000Hz pos 0 0 scale 1.5 transform normal adaptive_sync off
+# output \"Some Description 1\" scale 1.5
+output \"Some Description 2\" disable
+"
+        );
+        assert_eq!(render(&rendered, &monitors).unwrap(), rendered);
+    }
+}
@@ -16,6 +16,7 @@ pub mod tests {
                 ],
                 position: Some(Position { x: 0, y: 0 }),
                 scale: Some(1.0),
+                ..Default::default()
             },
             Monitor {
                 name: "Monitor 2".to_string(),
@@ -30,6 +31,7 @@ pub mod tests {
                 ],
                 position: Some(Position { x: 1920, y: 0 }),
                 scale: Some(1.25),
+                ..Default::default()
             },
         ]
     }
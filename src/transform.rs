@@ -0,0 +1,159 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::utils::TransformValue;
+use crate::utils::TUIMode;
+use crate::App;
+
+#[derive(Debug)]
+pub struct Transform{
+    pub state: TableState,
+}
+
+impl Transform{
+    pub fn new(selected:usize) -> Self {
+        Transform {
+            state: TableState::default()
+                .with_selected(selected),
+        }
+    }
+
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('k')=> Transform::previous(app),
+            KeyCode::Char('j')=> Transform::next(app),
+            KeyCode::Char(' ')=> Transform::select(app),
+            KeyCode::Esc => Transform::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    fn next(app:&mut App) {
+        app.selected_transform = if app.selected_transform >= TransformValue::table().len() - 1 {
+            0
+        } else {
+            app.selected_transform + 1
+        }
+    }
+
+    fn previous(app:&mut App) {
+        app.selected_transform = if app.selected_transform == 0 {
+            TransformValue::table().len() - 1
+        } else {
+            app.selected_transform - 1
+        }
+    }
+
+    fn select(app:&mut App) {
+        app.monitors[app.selected_monitor].transform = TransformValue::table()[app.selected_transform].value;
+    }
+
+
+
+    fn transforms_to_rows(&self) -> Vec<Row<'static>> {
+        
+        TransformValue::table()
+            .into_iter()
+            .map(|transform| {
+                Row::new(vec![
+                    Cell::default().content(
+                        Line::from(transform.name)
+                            .centered()
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Transform{
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Transform ".bold());
+        let block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+
+        let widths = [
+            Constraint::Percentage(100),
+        ];   
+
+        let table = Table::new(self.transforms_to_rows(),widths) 
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol("  ")            
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn render_transform() {
+
+        let mut transforms = Transform{
+            state: TableState::default(),
+        }; 
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 10));
+        
+        transforms.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec![
+            "┏━━━ Transform ━━━━┓",
+            "┃      Normal      ┃",
+            "┃       90°        ┃",
+            "┃       180°       ┃",
+            "┃       270°       ┃",
+            "┃     Flipped      ┃",
+            "┃   Flipped 90°    ┃",
+            "┃   Flipped 180°   ┃",
+            "┃   Flipped 270°   ┃",
+            "┗━━━━━━━━━━━━━━━━━━┛",
+        ]);
+
+        let border_style = Style::new().fg(Color::Yellow);
+        let title_style = Style::new().bold().fg(Color::White);
+        let row_style = Style::new();
+
+        // first line : title
+        expected.set_style(Rect::new(0, 0, 4, 1), border_style);
+        expected.set_style(Rect::new(4, 0, 11, 1), title_style);
+        expected.set_style(Rect::new(15, 0, 5, 1), border_style);       
+
+        // second line : row 
+        for i in 0..TransformValue::table().len() {
+            expected.set_style(Rect::new(0, (i + 1) as u16, 1, 1), border_style);
+            expected.set_style(Rect::new(1, (i + 1) as u16, 18, 1), row_style);
+            expected.set_style(Rect::new(19, (i + 1) as u16, 1, 1), border_style);
+        }
+
+        // last line : instructions 
+        expected.set_style(Rect::new(0,9, 20, 1), border_style);
+
+        assert_eq!(buf, expected);
+    }
+}
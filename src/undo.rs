@@ -0,0 +1,139 @@
+use crate::monitor::Monitor;
+
+pub const MAX_DEPTH: usize = 100;
+
+// snapshots of the monitors taken before each edit
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    undo: Vec<Vec<Monitor>>,
+    redo: Vec<Vec<Monitor>>,
+    coalescing: bool,
+}
+
+impl UndoHistory {
+    // consecutive edits recorded with coalesce share the snapshot taken before the first one
+    pub fn record(&mut self, before: Vec<Monitor>, coalesce: bool) {
+        if !(coalesce && self.coalescing) {
+            self.undo.push(before);
+            if self.undo.len() > MAX_DEPTH {
+                self.undo.remove(0);
+            }
+        }
+        self.coalescing = coalesce;
+        self.redo.clear();
+    }
+
+    pub fn break_coalescing(&mut self) {
+        self.coalescing = false;
+    }
+
+    pub fn undo(&mut self, monitors: &mut Vec<Monitor>) -> bool {
+        self.coalescing = false;
+        let Some(previous) = self.undo.pop() else {
+            return false;
+        };
+        let restored = restore(previous, monitors);
+        self.redo.push(std::mem::replace(monitors, restored));
+        true
+    }
+
+    pub fn redo(&mut self, monitors: &mut Vec<Monitor>) -> bool {
+        self.coalescing = false;
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        let restored = restore(next, monitors);
+        self.undo.push(std::mem::replace(monitors, restored));
+        true
+    }
+
+    pub fn depth(&self) -> (usize, usize) {
+        (self.undo.len(), self.redo.len())
+    }
+
+    pub fn clear(&mut self) {
+        *self = UndoHistory::default();
+    }
+}
+
+// a snapshot taken before a hotplug only restores the outputs that are still connected
+fn restore(snapshot: Vec<Monitor>, monitors: &[Monitor]) -> Vec<Monitor> {
+    monitors.iter()
+        .map(|monitor| match snapshot.iter().find(|saved| saved.key() == monitor.key()) {
+            Some(saved) if !saved.disconnected && !monitor.disconnected => Monitor {
+                name: monitor.name.clone(),
+                ..saved.clone()
+            },
+            _ => monitor.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::Position;
+    use crate::test_utils::tests::test_monitors;
+
+    #[test]
+    fn undo_and_redo() {
+        let mut history = UndoHistory::default();
+        let mut monitors = test_monitors();
+        assert!(!history.undo(&mut monitors));
+
+        // three nudges are undone at once
+        for _ in 0..3 {
+            history.record(monitors.clone(), true);
+            monitors[0].move_horizontal(10);
+        }
+        history.record(monitors.clone(), false);
+        monitors[0].scale = Some(2.0);
+        assert_eq!(history.depth(), (2, 0));
+
+        assert!(history.undo(&mut monitors));
+        assert_eq!(monitors[0].scale, Some(1.0));
+        assert!(history.undo(&mut monitors));
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(history.depth(), (0, 2));
+
+        assert!(history.redo(&mut monitors));
+        assert_eq!(monitors[0].position, Some(Position { x: 30, y: 0 }));
+
+        // a new edit drops the redo stack
+        history.record(monitors.clone(), false);
+        monitors[0].enabled = false;
+        assert_eq!(history.depth(), (2, 0));
+        assert!(!history.redo(&mut monitors));
+
+        for _ in 0..MAX_DEPTH + 10 {
+            history.record(monitors.clone(), false);
+        }
+        assert_eq!(history.depth(), (MAX_DEPTH, 0));
+    }
+
+    #[test]
+    fn undo_across_hotplug() {
+        let mut history = UndoHistory::default();
+        let mut monitors = test_monitors();
+        history.record(monitors.clone(), false);
+        monitors[0].scale = Some(2.0);
+        monitors[1].scale = Some(2.0);
+
+        // Monitor 2 is unplugged and a projector is plugged in
+        monitors[1].disconnected = true;
+        let mut projector = test_monitors()[0].clone();
+        projector.name = "HDMI-A-1".to_string();
+        projector.serial = Some("3".to_string());
+        monitors.push(projector.clone());
+
+        assert!(history.undo(&mut monitors));
+        assert_eq!(monitors.len(), 3);
+        assert_eq!(monitors[0].scale, Some(1.0));
+        assert_eq!(monitors[1].scale, Some(2.0));
+        assert!(monitors[1].disconnected);
+        assert_eq!(monitors[2], projector);
+
+        assert!(history.redo(&mut monitors));
+        assert_eq!(monitors[0].scale, Some(2.0));
+    }
+}
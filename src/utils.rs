@@ -4,7 +4,36 @@ pub enum TUIMode {
     View,
     Move,
     Resolution,
-    Scale
+    Scale,
+    Apply,
+    Profiles,
+    Transform,
+    Mirror,
+    SaveConfirm,
+    Arrange,
+    QuitConfirm,
+    Backups,
+    RandrConfirm,
+}
+
+impl TUIMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TUIMode::View => "View",
+            TUIMode::Move => "Move",
+            TUIMode::Resolution => "Resolution",
+            TUIMode::Scale => "Scale",
+            TUIMode::Apply => "Apply",
+            TUIMode::Profiles => "Profiles",
+            TUIMode::Transform => "Transform",
+            TUIMode::Mirror => "Mirror",
+            TUIMode::SaveConfirm => "Save",
+            TUIMode::Arrange => "Arrange",
+            TUIMode::QuitConfirm => "Quit",
+            TUIMode::Backups => "Backups",
+            TUIMode::RandrConfirm => "wlr-randr",
+        }
+    }
 }
 
 pub struct ScaleValue {
@@ -29,3 +58,25 @@ impl ScaleValue {
         ]
     }
 }
+
+pub struct TransformValue {
+    pub name: &'static str,
+    pub value: u8,
+}
+impl TransformValue {
+    pub fn new(name: &'static str, value: u8) -> Self {
+        TransformValue { name, value }
+    }
+    pub fn table() -> Vec<Self> {
+        vec![
+            TransformValue::new("Normal", 0),
+            TransformValue::new("90°", 1),
+            TransformValue::new("180°", 2),
+            TransformValue::new("270°", 3),
+            TransformValue::new("Flipped", 4),
+            TransformValue::new("Flipped 90°", 5),
+            TransformValue::new("Flipped 180°", 6),
+            TransformValue::new("Flipped 270°", 7),
+        ]
+    }
+}
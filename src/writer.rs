@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::backend::WlrRandr;
+use crate::backup;
+use crate::configuration::Configuration;
+use crate::diff::{self, DiffLine};
+use crate::error;
+use crate::hyprland;
+use crate::kanshi;
+use crate::monitor::Monitor;
+use crate::niri;
+use crate::profile;
+use crate::sway;
+
+pub trait ConfigWriter: std::fmt::Debug {
+    // reads back the settings of an existing file into the monitors
+    fn merge(&self, _content: &str, _monitors: &mut [Monitor]) {}
+    // content of the file once the monitors are written into the existing one
+    fn render(&self, existing: &str, monitors: &[Monitor]) -> error::Result<String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigFormat {
+    Hyprland,
+    Sway,
+    Kanshi,
+    Niri,
+    WlrRandr,
+}
+
+impl ConfigFormat {
+    pub fn writer(self) -> Box<dyn ConfigWriter> {
+        match self {
+            ConfigFormat::Hyprland => Box::new(HyprlandWriter),
+            ConfigFormat::Sway => Box::new(SwayWriter),
+            ConfigFormat::Kanshi => Box::new(KanshiWriter),
+            ConfigFormat::Niri => Box::new(NiriWriter),
+            ConfigFormat::WlrRandr => Box::new(WlrRandrWriter),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HyprlandWriter;
+
+impl ConfigWriter for HyprlandWriter {
+    fn merge(&self, content: &str, monitors: &mut [Monitor]) {
+        hyprland::merge(content, monitors);
+    }
+
+    fn render(&self, existing: &str, monitors: &[Monitor]) -> error::Result<String> {
+        hyprland::render(existing, monitors)
+    }
+}
+
+#[derive(Debug)]
+pub struct SwayWriter;
+
+impl ConfigWriter for SwayWriter {
+    fn merge(&self, content: &str, monitors: &mut [Monitor]) {
+        sway::merge(content, monitors);
+    }
+
+    fn render(&self, existing: &str, monitors: &[Monitor]) -> error::Result<String> {
+        sway::render(existing, monitors)
+    }
+}
+
+#[derive(Debug)]
+pub struct KanshiWriter;
+
+impl ConfigWriter for KanshiWriter {
+    fn render(&self, existing: &str, monitors: &[Monitor]) -> error::Result<String> {
+        let name = kanshi::layout_name(monitors);
+        Ok(kanshi::merge(existing, &name, &kanshi::profile_block(&name, &profile::from_monitors(monitors))))
+    }
+}
+
+#[derive(Debug)]
+pub struct NiriWriter;
+
+impl ConfigWriter for NiriWriter {
+    fn render(&self, existing: &str, monitors: &[Monitor]) -> error::Result<String> {
+        niri::render(existing, monitors)
+    }
+}
+
+// a shell script applying the layout with wlr-randr, rewritten as a whole
+#[derive(Debug)]
+pub struct WlrRandrWriter;
+
+impl ConfigWriter for WlrRandrWriter {
+    fn render(&self, _existing: &str, monitors: &[Monitor]) -> error::Result<String> {
+        let mut script = "#!/bin/sh\n# generated by display-tui\nwlr-randr".to_string();
+        for arg in WlrRandr::apply_args(monitors) {
+            if arg == "--output" {
+                script.push_str(" \\\n ");
+            }
+            script.push(' ');
+            script.push_str(&WlrRandr::quote(&arg));
+        }
+        script.push('\n');
+        Ok(script)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Target {
+    pub format: ConfigFormat,
+    pub path: String,
+}
+
+impl Target {
+    pub fn expanded_path(&self) -> PathBuf {
+        PathBuf::from(shellexpand::tilde(&self.path).to_string())
+    }
+
+    pub fn read(&self) -> error::Result<String> {
+        match std::fs::read_to_string(self.expanded_path()) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // current content of the file and the content that would be written
+    pub fn preview(&self, monitors: &[Monitor]) -> error::Result<(String, String)> {
+        let existing = self.read()?;
+        let rendered = self.format.writer().render(&existing, monitors)?;
+        Ok((existing, rendered))
+    }
+
+    pub fn save(&self, monitors: &[Monitor], backups: usize) -> error::Result<()> {
+        let (_, rendered) = self.preview(monitors)?;
+        let path = self.expanded_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        backup::create(&path, &rendered, backups)?;
+        backup::write_atomic(&path, &rendered)?;
+        if self.format == ConfigFormat::WlrRandr {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(())
+    }
+}
+
+// diff of every target, each one under the path of its file when there are several
+pub fn diff(config: &Configuration, monitors: &[Monitor]) -> error::Result<Vec<DiffLine>> {
+    // the rules of disconnected outputs are left as they are in the files
+    let monitors = &Monitor::connected(monitors);
+    let targets = config.targets();
+    let mut lines = vec![];
+    for target in &targets {
+        let (existing, rendered) = target.preview(monitors)?;
+        let changes = diff::unified(&existing, &rendered, diff::CONTEXT);
+        if targets.len() > 1 && !changes.is_empty() {
+            lines.push(DiffLine::File(target.path.clone()));
+        }
+        lines.extend(changes);
+    }
+    Ok(lines)
+}
+
+// every target is rendered before the first one is written
+pub fn save(config: &Configuration, monitors: &[Monitor]) -> error::Result<()> {
+    let monitors = &Monitor::connected(monitors);
+    let targets = config.targets();
+    for target in &targets {
+        target.preview(monitors)?;
+    }
+    for target in &targets {
+        target.save(monitors, config.backups)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    fn render(format: ConfigFormat) -> String {
+        let mut monitors = test_monitors();
+        // a third output keeps the disabled case while the second one sets every setting
+        let mut disabled = monitors[1].clone();
+        disabled.name = "Monitor 3".to_string();
+        disabled.serial = Some("3".to_string());
+        monitors.push(disabled);
+        monitors[1].enabled = true;
+        monitors[1].transform = 1;
+        monitors[1].vrr = true;
+        format.writer().render("", &monitors).unwrap()
+    }
+
+    #[test]
+    fn golden_files() {
+        let golden = [
+            (ConfigFormat::Hyprland, include_str!("../tests/golden/hyprland.conf")),
+            (ConfigFormat::Sway, include_str!("../tests/golden/sway.conf")),
+            (ConfigFormat::Kanshi, include_str!("../tests/golden/kanshi.conf")),
+            (ConfigFormat::Niri, include_str!("../tests/golden/niri.kdl")),
+            (ConfigFormat::WlrRandr, include_str!("../tests/golden/wlr-randr.sh")),
+        ];
+        for (format, expected) in golden {
+            assert_eq!(render(format), expected, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn save_every_target() -> std::io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("display-tui-targets-{}", std::process::id()));
+        let config = Configuration {
+            monitors_config_path: dir.join("monitors.conf").to_string_lossy().to_string(),
+            targets: vec![
+                Target { format: ConfigFormat::Niri, path: dir.join("niri/config.kdl").to_string_lossy().to_string() },
+                Target { format: ConfigFormat::WlrRandr, path: dir.join("layout.sh").to_string_lossy().to_string() },
+            ],
+            ..Default::default()
+        };
+        let monitors = test_monitors();
+
+        let lines = diff(&config, &monitors).unwrap();
+        let files: Vec<&DiffLine> = lines.iter().filter(|line| matches!(line, DiffLine::File(_))).collect();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[1].text(), format!("=== {}", dir.join("niri/config.kdl").display()));
+
+        save(&config, &monitors).unwrap();
+        assert!(std::fs::read_to_string(dir.join("monitors.conf"))?.starts_with("monitor = desc:Some Description 1"));
+        assert!(std::fs::read_to_string(dir.join("niri/config.kdl"))?.starts_with("output \"Some Description 1\" {"));
+        assert!(diff(&config, &monitors).unwrap().is_empty());
+
+        // nothing is written when one of the targets cannot be rendered
+        let mut broken = test_monitors();
+        broken[0].position = None;
+        std::fs::remove_file(dir.join("layout.sh"))?;
+        assert!(save(&config, &broken).is_err());
+        assert!(!dir.join("layout.sh").exists());
+
+        std::fs::remove_dir_all(&dir)
+    }
+}
@@ -0,0 +1,58 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+// a wlr-randr on PATH printing the fixture outputs and recording the arguments it was applied with
+const FAKE_WLR_RANDR: &str = "#!/bin/sh
+if [ \"$1\" = \"--json\" ]; then
+    cat \"$(dirname \"$0\")/outputs.json\"
+else
+    printf '%s\\n' \"$@\" > \"$(dirname \"$0\")/applied\"
+fi
+";
+
+fn display_tui(home: &Path, args: &[&str]) -> Output {
+    let path = format!("{}:{}", home.join("bin").display(), std::env::var("PATH").unwrap_or_default());
+    Command::new(env!("CARGO_BIN_EXE_display-tui"))
+        .args(args)
+        .env("HOME", home)
+        .env("PATH", path)
+        .env_remove("SWAYSOCK")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn apply_with_fake_wlr_randr() {
+    let home = std::env::temp_dir().join(format!("display-tui-home-{}", std::process::id()));
+    let bin = home.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(bin.join("outputs.json"), include_str!("fixtures/wlr_randr_outputs.json")).unwrap();
+    fs::write(bin.join("wlr-randr"), FAKE_WLR_RANDR).unwrap();
+    fs::set_permissions(bin.join("wlr-randr"), fs::Permissions::from_mode(0o755)).unwrap();
+    let monitors_config = home.join(".config/hypr/hyprland/monitors.conf");
+    fs::create_dir_all(monitors_config.parent().unwrap()).unwrap();
+    fs::write(&monitors_config, "monitor = DP-3, 1920x1080@60, 0x0, 1\nmonitor = eDP-1, disabled\n").unwrap();
+
+    let output = display_tui(&home, &["apply", "--dry-run"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "wlr-randr --output eDP-1 --off --output DP-3 --on --mode 1920x1080@60Hz --pos 0,0 --scale 1 --transform normal\n"
+    );
+    assert!(!bin.join("applied").exists());
+
+    let output = display_tui(&home, &["apply"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        fs::read_to_string(bin.join("applied")).unwrap(),
+        "--output\neDP-1\n--off\n--output\nDP-3\n--on\n--mode\n1920x1080@60Hz\n--pos\n0,0\n--scale\n1\n--transform\nnormal\n"
+    );
+
+    let output = display_tui(&home, &["apply", "missing"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "Unknown profile missing\n");
+
+    fs::remove_dir_all(&home).unwrap();
+}
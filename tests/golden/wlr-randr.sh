@@ -0,0 +1,6 @@
+#!/bin/sh
+# generated by display-tui
+wlr-randr \
+  --output 'Monitor 1' --on --mode 1920x1080@60Hz --pos 0,0 --scale 1 --transform normal \
+  --output 'Monitor 2' --on --mode 1280x720@60Hz --pos 1920,0 --scale 1.25 --transform 90 \
+  --output 'Monitor 3' --off